@@ -0,0 +1,87 @@
+//!
+//! Lexer throughput over a few representative JSON5 documents.
+//!
+//! Run with `cargo bench --bench lexing`.
+//!
+
+use std::hint::black_box;
+
+use avjason::{common::SourceFile, lex::lex_all_recovering};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+///
+/// Roughly how large each generated document is, in bytes.
+///
+const SIZE: usize = 256 << 10;
+
+///
+/// Repeat `item(i)` for `i = 0, 1, ...` until the text is about [`SIZE`] bytes.
+///
+fn repeat(mut item: impl FnMut(usize) -> String) -> String {
+    let mut text = String::with_capacity(SIZE + 256);
+    let mut i = 0;
+    while text.len() < SIZE {
+        text.push_str(&item(i));
+        i += 1;
+    }
+    text
+}
+
+///
+/// Arrays of integers, decimals, exponents and hex literals.
+///
+fn numbers() -> String {
+    let body = repeat(|i| {
+        format!(
+            "  [{i}, -{i}.25, +.{i}e-7, 0x{i:X}, {}E+{}, Infinity, NaN],\n",
+            i * 31,
+            i % 300
+        )
+    });
+    format!("[\n{body}]\n")
+}
+
+///
+/// Objects of strings, in both quote styles and with escapes.
+///
+fn strings() -> String {
+    let body = repeat(|i| {
+        format!(
+            "  {{ name: 'item {i}', \"description\": \"a \\\"quoted\\\" \\u00e9scape\\tand a tab\", \
+             path: 'C:\\\\dir\\\\{i}.json5', note: \"line \\\n continued\" }},\n"
+        )
+    });
+    format!("[\n{body}]\n")
+}
+
+///
+/// Objects and arrays nested a hundred deep, with comments between them.
+///
+fn nested() -> String {
+    let open = "{ a: [ // deeper\n".repeat(100);
+    let close = "] /* back out */ }\n".repeat(100);
+    let body = repeat(|i| format!("{open}{i}{close},\n"));
+    format!("[\n{body}]\n")
+}
+
+fn lexing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lexing");
+
+    for (name, text) in [
+        ("numbers", numbers()),
+        ("strings", strings()),
+        ("nested", nested()),
+    ] {
+        let src = SourceFile::dummy_file(text);
+        let (_, errors) = lex_all_recovering(&src);
+        assert!(errors.is_empty(), "{name} should lex cleanly: {errors:?}");
+
+        group.throughput(Throughput::Bytes(src.text().len() as u64));
+        group.bench_function(name, |b| b.iter(|| lex_all_recovering(black_box(&src))));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, lexing);
+criterion_main!(benches);
@@ -0,0 +1,90 @@
+//!
+//! Time and memory used to load and lex a multi-megabyte file.
+//!
+//! Run with `cargo bench --bench source`.
+//!
+
+#[path = "../tests/common/alloc.rs"]
+mod alloc;
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use avjason::{
+    common::{SourceFile, Span},
+    lex::lex_all_recovering,
+};
+
+use self::alloc::counting;
+
+///
+/// A document of roughly `size` bytes, mixing ASCII and non-ASCII text.
+///
+fn document(size: usize) -> String {
+    let mut text = String::from("[\n");
+    let mut i = 0;
+    while text.len() < size {
+        text.push_str(&format!(
+            "  {{ id: {i}, name: 'item {i}', ratio: {}.5e-3, tags: [\"ünïcødé\", \"💩\", 0x{i:x}] }}, // #{i}\n",
+            i % 97,
+        ));
+        i += 1;
+    }
+    text.push_str("]\n");
+    text
+}
+
+fn best_of(runs: usize, mut f: impl FnMut()) -> Duration {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let text = document(4 << 20);
+    let chars = text.chars().count();
+    let mb = text.len() as f64 / (1 << 20) as f64;
+    println!("document: {mb:.1} MiB, {chars} chars");
+
+    // Everything the file holds on to besides its text,
+    // including lazily-built tables, once they exist.
+    let (src, counts) = counting(|| {
+        let src = SourceFile::dummy_file(text.clone());
+        src.line_index();
+        black_box(src.slice(Span::new(chars - 2, chars)));
+        src
+    });
+    let overhead = counts.live as usize - text.len();
+    println!(
+        "memory:   {:.1} MiB of buffers besides the text ({:.2} bytes/char)",
+        overhead as f64 / (1 << 20) as f64,
+        overhead as f64 / chars as f64,
+    );
+    drop(src);
+
+    let load = best_of(5, || {
+        black_box(SourceFile::dummy_file(text.clone()));
+    });
+    println!("load:     {load:?}");
+
+    let src = SourceFile::dummy_file(text.clone());
+    let ((elements, _), counts) = counting(|| black_box(lex_all_recovering(&src)));
+    println!(
+        "elements: {} ({} reallocations)",
+        elements.len(),
+        counts.reallocations,
+    );
+    drop(elements);
+
+    let lex = best_of(5, || {
+        black_box(lex_all_recovering(&src));
+    });
+    println!("lex:      {lex:?} ({:.1} MiB/s)", mb / lex.as_secs_f64());
+}
@@ -0,0 +1,34 @@
+//!
+//! Lex arbitrary input, decoding every token, which should only ever
+//! fail with a `LexError`, never a panic.
+//!
+
+#![no_main]
+
+use avjason::{
+    common::SourceFile,
+    lex::{lex_all_recovering, tokens::Token, InputElement},
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let src = SourceFile::dummy_file(String::from_utf8_lossy(data));
+    let (elements, _errors) = lex_all_recovering(&src);
+
+    for element in &elements {
+        match element {
+            InputElement::Token(Token::String(string)) => {
+                let _ = string.value(&src);
+            }
+            InputElement::Token(Token::Number(number)) => {
+                let _ = number.as_f64(&src);
+                let _ = number.as_i64(&src);
+                let _ = number.as_u64(&src);
+            }
+            InputElement::Token(Token::Identifier(ident)) => {
+                let _ = ident.name(&src);
+            }
+            _ => {}
+        }
+    }
+});
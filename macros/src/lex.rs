@@ -0,0 +1,276 @@
+//!
+//! `#[derive(Lex)]`
+//!
+
+use std::cmp::Reverse;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Attribute, Data, DataEnum, DeriveInput, Fields, Ident, Lit, LitInt, Token, Type, Variant,
+};
+
+use crate::spanned::is_span_type;
+
+pub fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let (peek, lex) = match &input.data {
+        Data::Enum(data) => lex_for_enum(ident, &input.attrs, data)?,
+        Data::Struct(data) => lex_for_struct(ident, &input.attrs, &data.fields)?,
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "`Lex` cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::avjason::lex::Lex for #ident #ty_generics #where_clause {
+            fn peek(input: &::avjason::common::SourceIter) -> bool {
+                #peek
+            }
+
+            fn lex(
+                input: &mut ::avjason::common::SourceIter,
+            ) -> ::core::result::Result<Self, ::avjason::lex::LexError> {
+                #lex
+            }
+        }
+    })
+}
+
+///
+/// A struct is a token written exactly as the literal in its `#[lex(...)]` attribute,
+/// holding only its span.
+///
+fn lex_for_struct(
+    ident: &Ident,
+    attrs: &[Attribute],
+    fields: &Fields,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    let attr = match lex_attrs(attrs)[..] {
+        [attr] => attr,
+        [] => {
+            let message = "a struct deriving `Lex` needs the literal it is written as, \
+                           like `#[lex(\"=>\")]`";
+            return Err(syn::Error::new(ident.span(), message));
+        }
+        [_, second, ..] => {
+            return Err(syn::Error::new_spanned(
+                second,
+                "a token can only be written as one literal",
+            ))
+        }
+    };
+
+    let Literal {
+        text: literal,
+        ignore_case,
+        keyword,
+    } = attr.parse_args()?;
+    let len = literal.chars().count();
+
+    let only_span = format!("`{ident}` must hold only its span, like `struct {ident}(Span)`");
+    let field = match fields {
+        Fields::Named(fields) if fields.named.len() == 1 => &fields.named[0],
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+        Fields::Unit => return Err(syn::Error::new(ident.span(), only_span)),
+        _ => return Err(syn::Error::new_spanned(fields, only_span)),
+    };
+
+    if !is_span_type(&field.ty) {
+        return Err(syn::Error::new_spanned(&field.ty, only_span));
+    }
+
+    let build = match &field.ident {
+        Some(name) => quote! { Self { #name: span } },
+        None => quote! { Self(span) },
+    };
+
+    let mut peek = match ignore_case {
+        false => quote! { input.peek_str(#literal) },
+        true => quote! { input.peek_str_ignore_case(#literal) },
+    };
+
+    if keyword {
+        peek = quote! {
+            #peek && {
+                let mut after = input.fork();
+                for _ in 0..#len {
+                    ::core::iter::Iterator::next(&mut after);
+                }
+
+                !::avjason::lex::identifier::LIdentifier::is_identifier_part(&after)
+            }
+        };
+    }
+
+    let lex = quote! {
+        if !(#peek) {
+            return ::core::result::Result::Err(::avjason::lex::LexError::unexpected(input));
+        }
+
+        let start = input.offset();
+        for _ in 0..#len {
+            ::core::iter::Iterator::next(input);
+        }
+
+        let span = ::avjason::common::Span::new(start, input.offset());
+        ::core::result::Result::Ok(#build)
+    };
+
+    Ok((peek, lex))
+}
+
+///
+/// The contents of a struct's `#[lex(...)]` attribute:
+/// the literal the token is written as, then any options.
+///
+struct Literal {
+    text: String,
+    ignore_case: bool,
+    keyword: bool,
+}
+
+impl Parse for Literal {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let text = match input.parse()? {
+            Lit::Char(ch) => ch.value().to_string(),
+            Lit::Str(s) if !s.value().is_empty() => s.value(),
+            lit => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "expected a character or non-empty string literal",
+                ))
+            }
+        };
+
+        let mut literal = Self {
+            text,
+            ignore_case: false,
+            keyword: false,
+        };
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let option: Ident = input.parse()?;
+            match option.to_string().as_str() {
+                "ignore_case" => literal.ignore_case = true,
+                "keyword" => literal.keyword = true,
+                _ => {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "expected `ignore_case` or `keyword`",
+                    ))
+                }
+            }
+        }
+
+        Ok(literal)
+    }
+}
+
+///
+/// An enum of tokens is lexed as whichever of its variants
+/// peeks first, trying them in the order they are declared,
+/// except that variants with a higher `#[lex(priority = N)]` are tried first.
+///
+fn lex_for_enum(
+    ident: &Ident,
+    attrs: &[Attribute],
+    data: &DataEnum,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    if let Some(attr) = lex_attrs(attrs).first() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "an enum is lexed as one of its variants, so it cannot be written as a literal",
+        ));
+    }
+
+    if data.variants.is_empty() {
+        return Err(syn::Error::new(
+            ident.span(),
+            format!("`{ident}` needs at least one variant to lex"),
+        ));
+    }
+
+    let mut variants = data
+        .variants
+        .iter()
+        .map(|variant| {
+            Ok((
+                priority(variant)?,
+                &variant.ident,
+                variant_token(&variant.ident, &variant.fields)?,
+            ))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    // Stable, so variants of the same priority keep their declared order.
+    variants.sort_by_key(|&(priority, ..)| Reverse(priority));
+
+    let tokens = variants.iter().map(|(_, _, ty)| ty);
+    let peek = quote! {
+        false #(|| <#tokens as ::avjason::lex::Lex>::peek(input))*
+    };
+
+    let attempts = variants.iter().map(|(_, ident, ty)| {
+        quote! {
+            if <#ty as ::avjason::lex::Lex>::peek(input) {
+                return <#ty as ::avjason::lex::Lex>::lex(input).map(Self::#ident);
+            }
+        }
+    });
+    let lex = quote! {
+        #(#attempts)*
+        ::core::result::Result::Err(::avjason::lex::LexError::unexpected(input))
+    };
+
+    Ok((peek, lex))
+}
+
+///
+/// The priority given to `variant` by `#[lex(priority = N)]`, or 0.
+///
+fn priority(variant: &Variant) -> syn::Result<i32> {
+    let mut priority = 0;
+    for attr in lex_attrs(&variant.attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("priority") {
+                priority = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("expected `priority = N`"))
+            }
+        })?;
+    }
+
+    Ok(priority)
+}
+
+fn lex_attrs(attrs: &[Attribute]) -> Vec<&Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("lex"))
+        .collect()
+}
+
+///
+/// The token type held by a variant, which must be its only field.
+///
+fn variant_token<'a>(ident: &Ident, fields: &'a Fields) -> syn::Result<&'a Type> {
+    let one_token = format!("`{ident}` must hold exactly one token, like `{ident}(Token)`");
+    match fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok(&fields.unnamed[0].ty),
+        Fields::Unit => Err(syn::Error::new(ident.span(), one_token)),
+        _ => Err(syn::Error::new_spanned(fields, one_token)),
+    }
+}
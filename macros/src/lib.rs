@@ -0,0 +1,27 @@
+//!
+//! Derive macros for [avjason](https://docs.rs/avjason).
+//!
+//! These are re-exported by avjason itself, which is where they are documented.
+//!
+
+mod lex;
+mod spanned;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+#[proc_macro_derive(Spanned, attributes(span, spanned))]
+pub fn derive_spanned(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    spanned::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(Lex, attributes(lex))]
+pub fn derive_lex(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    lex::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,337 @@
+//!
+//! `#[derive(Spanned)]`
+//!
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, Attribute, Data, DataEnum, DeriveInput, Field, Fields, Generics, Ident, Type,
+    Variant,
+};
+
+pub fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let mut used = vec![];
+
+    let body = match &input.data {
+        Data::Struct(data) => spanned_for_struct(ident, &data.fields, &mut used)?,
+        Data::Enum(data) => spanned_for_enum(data, &mut used)?,
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "`Spanned` cannot be derived for unions",
+            ))
+        }
+    };
+
+    let generics = with_bounds(&input.generics, &used);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::avjason::common::Spanned for #ident #ty_generics #where_clause {
+            fn span(&self) -> ::avjason::common::Span {
+                #body
+            }
+        }
+    })
+}
+
+///
+/// `generics`, with a `Spanned` bound added for the type of each of the `used` fields
+/// which mentions a type parameter.
+///
+/// Bounding the field types, rather than the parameters themselves,
+/// means a field of type `Option<T>` does not stop `T` from being `?Sized`,
+/// and a skipped `PhantomData<T>` does not need `T: Spanned` at all.
+///
+fn with_bounds(generics: &Generics, used: &[&Type]) -> Generics {
+    let params: Vec<_> = generics.type_params().map(|param| &param.ident).collect();
+    let mut generics = generics.clone();
+    let mut bounded = vec![];
+
+    for ty in used {
+        let key = quote!(#ty).to_string();
+        if !mentions(quote!(#ty), &params) || bounded.contains(&key) {
+            continue;
+        }
+
+        bounded.push(key);
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { #ty: ::avjason::common::Spanned });
+    }
+
+    generics
+}
+
+///
+/// Does `tokens` mention any of `params`?
+///
+fn mentions(tokens: TokenStream, params: &[&Ident]) -> bool {
+    tokens.into_iter().any(|tree| match tree {
+        TokenTree::Ident(ident) => params.iter().any(|param| **param == ident),
+        TokenTree::Group(group) => mentions(group.stream(), params),
+        _ => false,
+    })
+}
+
+fn spanned_for_struct<'a>(
+    ident: &Ident,
+    fields: &'a Fields,
+    used: &mut Vec<&'a Type>,
+) -> syn::Result<TokenStream> {
+    let (pattern, span) = spanned_fields(ident, fields, used)?;
+    Ok(quote! {
+        let Self #pattern = self;
+        #span
+    })
+}
+
+fn spanned_for_enum<'a>(data: &'a DataEnum, used: &mut Vec<&'a Type>) -> syn::Result<TokenStream> {
+    let arms = data
+        .variants
+        .iter()
+        .map(|variant| spanned_variant_arm(variant, used))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        match self {
+            #(#arms)*
+        }
+    })
+}
+
+///
+/// A match arm giving the span of one variant.
+///
+/// Variants marked `#[spanned(empty)]` have no fields,
+/// and always give an empty span at the start of the file.
+///
+fn spanned_variant_arm<'a>(
+    variant: &'a Variant,
+    used: &mut Vec<&'a Type>,
+) -> syn::Result<TokenStream> {
+    let ident = &variant.ident;
+
+    if is_empty(variant)? {
+        if !variant.fields.is_empty() {
+            return Err(syn::Error::new(
+                ident.span(),
+                "only variants without fields can be `#[spanned(empty)]`",
+            ));
+        }
+
+        return Ok(quote! {
+            Self::#ident { .. } => <::avjason::common::Span as ::core::default::Default>::default(),
+        });
+    }
+
+    if variant.fields.is_empty() {
+        return Err(syn::Error::new(
+            ident.span(),
+            format!(
+                "`{ident}` has no fields to take a span from, \
+                 mark it `#[spanned(empty)]` to give it an empty span"
+            ),
+        ));
+    }
+
+    let (pattern, span) = spanned_fields(ident, &variant.fields, used)?;
+    Ok(quote! { Self::#ident #pattern => #span, })
+}
+
+///
+/// Is `variant` marked `#[spanned(empty)]`?
+///
+fn is_empty(variant: &Variant) -> syn::Result<bool> {
+    let mut empty = false;
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("spanned"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("empty") {
+                empty = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `empty`"))
+            }
+        })?;
+    }
+
+    Ok(empty)
+}
+
+///
+/// A pattern binding the fields which make up the span of a struct or variant,
+/// and an expression for that span.
+///
+/// The span is taken from, in order of preference:
+/// 1. the field marked `#[span]`,
+/// 2. the field named `span`,
+/// 3. the only field of type `Span`,
+/// 4. every field, combined.
+///
+/// Fields marked `#[spanned(skip)]` are never considered.
+///
+/// The types of the fields used are added to `used`.
+///
+fn spanned_fields<'a>(
+    ident: &Ident,
+    fields: &'a Fields,
+    used: &mut Vec<&'a Type>,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    if fields.is_empty() {
+        return Err(syn::Error::new(
+            ident.span(),
+            format!("`{ident}` has no fields to take a span from"),
+        ));
+    }
+
+    let fields: Vec<_> = fields.iter().collect();
+    let candidates = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| Ok((!is_skipped(field)?).then_some(i)))
+        .filter_map(Result::transpose)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    if candidates.is_empty() {
+        return Err(syn::Error::new(
+            ident.span(),
+            format!("every field of `{ident}` is skipped, so there is no span to take"),
+        ));
+    }
+
+    let chosen = match span_field(&fields, &candidates)? {
+        Some(index) => vec![index],
+        None => candidates,
+    };
+    used.extend(chosen.iter().map(|&i| &fields[i].ty));
+
+    let bindings: Vec<_> = chosen.iter().map(|i| format_ident!("__field{i}")).collect();
+    let span = match &bindings[..] {
+        [first, rest @ ..] => quote! {
+            ::avjason::common::Spanned::span(#first)
+                #(.merge(::avjason::common::Spanned::span(#rest)))*
+        },
+        [] => unreachable!("there is at least one field"),
+    };
+
+    let pattern = match fields[0].ident {
+        Some(_) => {
+            let names = chosen.iter().map(|&i| &fields[i].ident);
+            quote! { { #(#names: #bindings,)* .. } }
+        }
+        None => {
+            let elements = (0..fields.len()).map(|i| match chosen.iter().position(|&c| c == i) {
+                Some(b) => {
+                    let binding = &bindings[b];
+                    quote! { #binding }
+                }
+                None => quote! { _ },
+            });
+            quote! { ( #(#elements),* ) }
+        }
+    };
+
+    Ok((pattern, span))
+}
+
+///
+/// Is `field` marked `#[spanned(skip)]`?
+///
+fn is_skipped(field: &Field) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("spanned"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `skip`"))
+            }
+        })?;
+    }
+
+    if let (true, Some(attr)) = (skip, span_attr(field)) {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "a field marked `#[spanned(skip)]` cannot also be marked `#[span]`",
+        ));
+    }
+
+    Ok(skip)
+}
+
+fn span_attr(field: &Field) -> Option<&Attribute> {
+    field.attrs.iter().find(|attr| attr.path().is_ident("span"))
+}
+
+///
+/// The index of the field which holds the span on its own, if any,
+/// out of the `candidates` which have not been skipped.
+///
+fn span_field(fields: &[&Field], candidates: &[usize]) -> syn::Result<Option<usize>> {
+    let marked: Vec<_> = candidates
+        .iter()
+        .copied()
+        .filter(|&i| span_attr(fields[i]).is_some())
+        .collect();
+
+    match marked[..] {
+        [index] => return Ok(Some(index)),
+        [_, second, ..] => {
+            return Err(syn::Error::new_spanned(
+                span_attr(fields[second]),
+                "only one field can be marked `#[span]`",
+            ))
+        }
+        [] => {}
+    }
+
+    let named_span = |i: &usize| {
+        fields[*i]
+            .ident
+            .as_ref()
+            .is_some_and(|ident| ident == "span")
+    };
+    if let Some(index) = candidates.iter().copied().find(named_span) {
+        return Ok(Some(index));
+    }
+
+    let typed: Vec<_> = candidates
+        .iter()
+        .copied()
+        .filter(|&i| is_span_type(&fields[i].ty))
+        .collect();
+
+    match typed[..] {
+        [_, second, ..] => Err(syn::Error::new_spanned(
+            &fields[second].ty,
+            "more than one field has type `Span`, mark the one to use with `#[span]`",
+        )),
+        [index] => Ok(Some(index)),
+        [] => Ok(None),
+    }
+}
+
+///
+/// Is `ty` spelt `Span`, or a path ending in `Span`?
+///
+pub fn is_span_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Span" && segment.arguments.is_none()),
+        _ => false,
+    }
+}
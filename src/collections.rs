@@ -0,0 +1,11 @@
+//!
+//! Hash maps, from `std` if it is available.
+//!
+//! Without `std` there is no source of randomness to seed the hasher,
+//! so these fall back to `hashbrown`'s default hasher.
+//!
+
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::{DefaultHashBuilder as RandomState, HashMap};
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{hash_map::RandomState, HashMap};
@@ -0,0 +1,70 @@
+//!
+//! Conversion from character offsets to lines and columns.
+//!
+
+use alloc::{vec, vec::Vec};
+
+use crate::lex::line_terminator::is_line_terminator;
+
+///
+/// Where each line of a [`SourceFile`](super::SourceFile) starts,
+/// for turning offsets into human-friendly locations.
+///
+/// Any [line terminator](is_line_terminator) ends a line,
+/// with `\r\n` counting as one.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    // Character offset of the start of each line, in order.
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    ///
+    /// Find the start of each line in `text`.
+    ///
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut chars = text.chars().enumerate().peekable();
+        while let Some((i, ch)) = chars.next() {
+            if ch == '\r' && chars.peek().is_some_and(|&(_, next)| next == '\n') {
+                continue;
+            }
+
+            if is_line_terminator(ch) {
+                line_starts.push(i + 1);
+            }
+        }
+
+        Self { line_starts }
+    }
+
+    ///
+    /// The number of lines: one more than the number of line breaks.
+    ///
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    ///
+    /// The 1-based line and column of the character at `offset`.
+    ///
+    /// Columns count characters. A line terminator is at the end
+    /// of the line it finishes.
+    ///
+    /// ```
+    /// use avjason::common::SourceFile;
+    ///
+    /// let src = SourceFile::dummy_file("{\r\n  a: 1\n}");
+    /// let index = src.line_index();
+    /// assert_eq!(index.location(0), (1, 1));
+    /// assert_eq!(index.location(5), (2, 3));
+    /// assert_eq!(index.location(10), (3, 1));
+    /// ```
+    ///
+    pub fn location(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let column = offset - self.line_starts[line - 1] + 1;
+        (line, column)
+    }
+}
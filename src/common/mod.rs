@@ -0,0 +1,91 @@
+//!
+//! Types shared between the lexer and parser.
+//!
+
+mod line_index;
+#[cfg(feature = "std")]
+mod reader;
+mod source;
+mod span;
+
+pub use line_index::LineIndex;
+#[cfg(feature = "std")]
+pub use reader::{ReaderStream, StreamError};
+pub use source::{Checkpoint, SourceFile, SourceIter, SourceStream};
+pub use span::{Span, SpanDisplay, Spanned};
+
+///
+/// Derives [`Spanned`](trait@Spanned) for a struct or enum.
+///
+/// The span of a struct is that of its field marked `#[span]`,
+/// otherwise the field named `span`,
+/// otherwise its only field of type [`Span`],
+/// otherwise all of its fields merged together.
+/// Tuple structs follow the same rules, except there is no field named `span`,
+/// so the only element of type [`Span`] is used, wherever it is.
+/// Each variant of an enum is treated like a struct.
+///
+/// Fields marked `#[spanned(skip)]`, such as cached values or markers
+/// which are not [`Spanned`](trait@Spanned) themselves, are left out entirely.
+///
+/// ```
+/// use avjason::common::{Span, Spanned};
+///
+/// #[derive(Spanned)]
+/// struct Pair {
+///     #[span]
+///     range: Span,
+///     inner: Span,
+/// }
+///
+/// let pair = Pair { range: Span::new(0, 5), inner: Span::new(1, 2) };
+/// assert_eq!(pair.span(), Span::new(0, 5));
+/// ```
+///
+/// For generic types, the type of each field making up the span
+/// is required to be [`Spanned`](trait@Spanned) where it mentions a type parameter.
+///
+/// An enum variant without any fields has no span to take,
+/// unless it is marked `#[spanned(empty)]`, which makes its span
+/// the empty span at the start of the file, [`Span::default()`]:
+///
+/// ```
+/// use avjason::common::{Span, Spanned};
+///
+/// #[derive(Spanned)]
+/// enum Entry {
+///     Present(Span),
+///     #[spanned(empty)]
+///     Missing,
+/// }
+///
+/// assert_eq!(Entry::Present(Span::new(1, 4)).span(), Span::new(1, 4));
+/// assert_eq!(Entry::Missing.span(), Span::default());
+/// ```
+///
+/// Having more than one field of type [`Span`] without saying which to use
+/// is an error, as is marking more than one field:
+///
+/// ```compile_fail
+/// use avjason::common::{Span, Spanned};
+///
+/// #[derive(Spanned)]
+/// struct Pair {
+///     a: Span,
+///     b: Span,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use avjason::common::{Span, Spanned};
+///
+/// #[derive(Spanned)]
+/// struct Pair {
+///     #[span]
+///     a: Span,
+///     #[span]
+///     b: Span,
+/// }
+/// ```
+///
+pub use avjason_macros::Spanned;
@@ -0,0 +1,247 @@
+//!
+//! Lexing from a reader, decoding its text as the lexer needs it.
+//!
+
+use std::{
+    error::Error,
+    fmt,
+    io::{self, ErrorKind, Read},
+    str,
+};
+
+use crate::lex::{Lex, LexError};
+
+use super::{Checkpoint, SourceFile, SourceIter};
+
+///
+/// How many bytes to read at a time.
+///
+const BLOCK: usize = 8 * 1024;
+
+///
+/// How many chars past the end of a token must have been read before
+/// the token can be trusted.
+///
+/// The furthest any token in this crate looks ahead is deciding whether
+/// a `-` starts a number: it needs to see all of `Infinity` after it,
+/// then check that no `\uXXXX` escape carries on the word. If one does,
+/// the `-` is a token on its own, and the lexer has looked this far past it.
+///
+const LOOKAHEAD: usize = "Infinity\\u0000".len();
+
+///
+/// A cursor which reads its text from an [`io::Read`] as it goes,
+/// rather than all at once like [`SourceFile::from_reader`].
+///
+/// The UTF-8 input is decoded a block at a time, whenever a token
+/// needs more of it. Spans are char offsets from the start of the input,
+/// and everything read so far is kept in [`ReaderStream::file`].
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use avjason::{
+///     common::{ReaderStream, Span, Spanned},
+///     lex::InputElement,
+/// };
+///
+/// let mut stream = ReaderStream::new(Cursor::new("[1, 2]"));
+/// let mut tokens = vec![];
+/// while !stream.is_eof().unwrap() {
+///     if let InputElement::Token(token) = stream.lex().unwrap() {
+///         tokens.push(token);
+///     }
+/// }
+/// assert_eq!(tokens.len(), 5);
+/// assert_eq!(tokens[3].span(), Span::new(4, 5));
+/// ```
+///
+#[derive(Debug)]
+pub struct ReaderStream<R> {
+    reader: R,
+    file: SourceFile,
+    position: Option<Checkpoint>,
+    // The start of a char split between two reads.
+    pending: Vec<u8>,
+    done: bool,
+}
+
+impl<R: Read> ReaderStream<R> {
+    ///
+    /// Start reading from `reader`. Nothing is read until it is needed.
+    ///
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            file: SourceFile::dummy_file(""),
+            position: None,
+            pending: vec![],
+            done: false,
+        }
+    }
+
+    ///
+    /// Everything read so far.
+    ///
+    pub fn file(&self) -> &SourceFile {
+        &self.file
+    }
+
+    ///
+    /// How many characters into the input the cursor is.
+    ///
+    pub fn offset(&self) -> usize {
+        self.iter().offset()
+    }
+
+    ///
+    /// Has all of the input been consumed?
+    ///
+    pub fn is_eof(&mut self) -> io::Result<bool> {
+        self.read_ahead(1)?;
+        Ok(self.iter().is_eof())
+    }
+
+    ///
+    /// Does the upcoming input look like the start of a `T`?
+    ///
+    pub fn peek<T: Lex>(&mut self) -> io::Result<bool> {
+        // The `-` itself, then everything looked at past it.
+        self.read_ahead(1 + LOOKAHEAD)?;
+        Ok(T::peek(&self.iter()))
+    }
+
+    ///
+    /// Consume a `T` from the input, reading more of it if needed.
+    ///
+    pub fn lex<T: Lex>(&mut self) -> Result<T, StreamError> {
+        self.read_ahead(1)?;
+        loop {
+            let mut input = self.iter();
+            let result = T::lex(&mut input);
+
+            // Lexing stopped too close to the end of what has been read,
+            // so might have gone differently with more: try again. At least
+            // double what is left to lex each time, so a token as long as
+            // the whole input is only lexed a logarithmic number of times.
+            if !self.done && self.file.char_len() - input.offset() < LOOKAHEAD {
+                let ahead = self.file.char_len() - self.offset();
+                self.read_ahead(2 * ahead.max(LOOKAHEAD))?;
+                continue;
+            }
+
+            self.position = Some(input.checkpoint());
+            return result.map_err(StreamError::Lex);
+        }
+    }
+
+    fn iter(&self) -> SourceIter<'_> {
+        let mut input = self.file.iter();
+        if let Some(position) = self.position {
+            input.restore(position);
+        }
+        input
+    }
+
+    ///
+    /// Read until there are at least `chars` left after the cursor,
+    /// or the input runs out.
+    ///
+    fn read_ahead(&mut self, chars: usize) -> io::Result<()> {
+        while !self.done && self.file.char_len() - self.offset() < chars {
+            self.read()?;
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Read and decode the next block of input.
+    ///
+    fn read(&mut self) -> io::Result<()> {
+        let mut block = [0; BLOCK];
+        let read = loop {
+            match self.reader.read(&mut block) {
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                result => break result?,
+            }
+        };
+
+        if read == 0 {
+            self.done = true;
+            return match self.pending.is_empty() {
+                true => Ok(()),
+                false => Err(invalid_utf8()),
+            };
+        }
+
+        self.pending.extend_from_slice(&block[..read]);
+        let valid = match str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            // Only the last char is cut off, and the rest of it is still to come.
+            Err(err) if err.error_len().is_none() => err.valid_up_to(),
+            Err(_) => return Err(invalid_utf8()),
+        };
+
+        let text = str::from_utf8(&self.pending[..valid]).expect("checked above");
+        self.file.push_str(text);
+        self.pending.drain(..valid);
+
+        // Skip a byte order mark, as `SourceFile::iter` would.
+        if self.position.is_none() && self.file.char_len() > 0 {
+            self.position = Some(self.file.iter().checkpoint());
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+}
+
+///
+/// Something went wrong whilst lexing from a [`ReaderStream`].
+///
+#[derive(Debug)]
+pub enum StreamError {
+    ///
+    /// The input could not be read, or was not valid UTF-8.
+    ///
+    Io(io::Error),
+
+    ///
+    /// The input could not be lexed.
+    ///
+    Lex(LexError),
+}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<LexError> for StreamError {
+    fn from(err: LexError) -> Self {
+        Self::Lex(err)
+    }
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => err.fmt(f),
+            Self::Lex(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for StreamError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Lex(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,533 @@
+//!
+//! Source files and a cursor over their characters.
+//!
+
+use alloc::{string::String, vec, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::cell::OnceCell as OnceLock;
+use core::{convert::Infallible, str::FromStr};
+#[cfg(feature = "std")]
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::lex::{Lex, LexError, LexOptions, Tokens, TokensWithTrivia};
+
+use super::{LineIndex, Span};
+
+///
+/// A JSON5 source file, loaded into memory.
+///
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    #[cfg(feature = "std")]
+    path: PathBuf,
+    text: String,
+    char_len: usize,
+    // Byte offset of every `CHUNK`th char. Empty if the text is all ASCII,
+    // where char and byte offsets are the same.
+    chunks: Vec<usize>,
+    line_index: OnceLock<LineIndex>,
+}
+
+///
+/// How many chars apart the entries of [`SourceFile::chunks`] are:
+/// finding a byte offset decodes at most this many chars.
+///
+const CHUNK: usize = 64;
+
+impl SourceFile {
+    ///
+    /// Read the source file at `path`.
+    ///
+    #[cfg(feature = "std")]
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            ..Self::new(text)
+        })
+    }
+
+    ///
+    /// Read a source file from `reader`, until it runs out.
+    ///
+    /// This reads everything before returning. To start lexing
+    /// before the reader runs out, use a [`ReaderStream`](super::ReaderStream).
+    ///
+    /// The input must be valid UTF-8, otherwise this returns an
+    /// [`InvalidData`](io::ErrorKind::InvalidData) error.
+    ///
+    #[cfg(feature = "std")]
+    pub fn from_reader(mut reader: impl Read) -> io::Result<Self> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        Ok(Self {
+            path: PathBuf::from("<reader>"),
+            ..Self::new(text)
+        })
+    }
+
+    ///
+    /// Add `more` to the end of the text, as a
+    /// [`ReaderStream`](super::ReaderStream) reads it in.
+    ///
+    #[cfg(feature = "std")]
+    pub(crate) fn push_str(&mut self, more: &str) {
+        if self.chunks.is_empty() && more.is_ascii() {
+            self.char_len += more.len();
+        } else {
+            if self.chunks.is_empty() {
+                // Until now, every char was a single byte.
+                self.chunks = (0..self.char_len).step_by(CHUNK).collect();
+            }
+
+            for (byte, _) in more.char_indices() {
+                if self.char_len.is_multiple_of(CHUNK) {
+                    self.chunks.push(self.text.len() + byte);
+                }
+                self.char_len += 1;
+            }
+        }
+
+        self.text.push_str(more);
+        self.line_index = OnceLock::new();
+    }
+
+    ///
+    /// Create an in-memory source file, mostly useful for testing.
+    ///
+    pub fn dummy_file(text: impl Into<String>) -> Self {
+        Self::new(text.into())
+    }
+
+    fn new(text: String) -> Self {
+        let (char_len, chunks) = if text.is_ascii() {
+            (text.len(), vec![])
+        } else {
+            let mut char_len = 0;
+            let mut chunks = vec![];
+            for (i, (byte, _)) in text.char_indices().enumerate() {
+                if i % CHUNK == 0 {
+                    chunks.push(byte);
+                }
+                char_len += 1;
+            }
+            (char_len, chunks)
+        };
+
+        Self {
+            #[cfg(feature = "std")]
+            path: PathBuf::from("<dummy>"),
+            text,
+            char_len,
+            chunks,
+            line_index: OnceLock::new(),
+        }
+    }
+
+    ///
+    /// Where this source file was loaded from.
+    ///
+    #[cfg(feature = "std")]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    ///
+    /// The full text of this source file.
+    ///
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    ///
+    /// The number of characters in this source file.
+    ///
+    pub fn char_len(&self) -> usize {
+        self.char_len
+    }
+
+    ///
+    /// Where each line of this file starts,
+    /// worked out the first time it is needed.
+    ///
+    pub fn line_index(&self) -> &LineIndex {
+        self.line_index.get_or_init(|| LineIndex::new(&self.text))
+    }
+
+    ///
+    /// The byte offset into [`SourceFile::text`] of the character
+    /// at `char_offset`.
+    ///
+    /// Offsets past the end of the file map to the end of the text.
+    ///
+    pub fn byte_offset(&self, char_offset: usize) -> usize {
+        if self.chunks.is_empty() {
+            return char_offset.min(self.text.len());
+        }
+
+        let Some(&start) = self.chunks.get(char_offset / CHUNK) else {
+            return self.text.len();
+        };
+
+        self.text[start..]
+            .char_indices()
+            .nth(char_offset % CHUNK)
+            .map_or(self.text.len(), |(i, _)| start + i)
+    }
+
+    ///
+    /// The text covered by `span`.
+    ///
+    pub fn slice(&self, span: Span) -> &str {
+        &self.text[span.byte_range(self)]
+    }
+
+    ///
+    /// A cursor over this file, starting at the first character.
+    ///
+    /// A byte order mark (U+FEFF) at the very start of the file is skipped.
+    /// Anywhere else, it is lexed as [whitespace](crate::lex::whitespace::WhiteSpace).
+    ///
+    pub fn iter(&self) -> SourceIter<'_> {
+        let bom = self.text.starts_with('\u{FEFF}');
+        self.iter_at(usize::from(bom))
+    }
+
+    ///
+    /// A cursor over this file, starting at the first character,
+    /// which lexes according to `options`.
+    ///
+    pub fn iter_with(&self, options: LexOptions) -> SourceIter<'_> {
+        SourceIter {
+            options,
+            ..self.iter()
+        }
+    }
+
+    ///
+    /// The [tokens](crate::lex::tokens::Token) of this file, leaving out
+    /// whitespace, line terminators and comments.
+    ///
+    /// Lexing carries on after an error, so every error is reported.
+    ///
+    /// ```
+    /// use avjason::common::{SourceFile, Spanned};
+    ///
+    /// let src = SourceFile::dummy_file("{ a: 1 } // done");
+    /// for token in src.tokens() {
+    ///     println!("{}", token.unwrap().raw(&src));
+    /// }
+    /// ```
+    ///
+    pub fn tokens(&self) -> Tokens<'_> {
+        Tokens::new(self.iter())
+    }
+
+    ///
+    /// Every element of this file, including whitespace, line terminators
+    /// and comments, as [`SourceFile::tokens`] would lex them.
+    ///
+    pub fn tokens_with_trivia(&self) -> TokensWithTrivia<'_> {
+        TokensWithTrivia::new(self.iter())
+    }
+
+    ///
+    /// A cursor over this file, starting at character `offset`.
+    ///
+    pub(crate) fn iter_at(&self, offset: usize) -> SourceIter<'_> {
+        SourceIter {
+            file: self,
+            index: offset,
+            byte: self.byte_offset(offset),
+            options: LexOptions::default(),
+        }
+    }
+}
+
+impl From<String> for SourceFile {
+    fn from(text: String) -> Self {
+        Self::dummy_file(text)
+    }
+}
+
+impl From<&str> for SourceFile {
+    fn from(text: &str) -> Self {
+        Self::dummy_file(text)
+    }
+}
+
+///
+/// Build an in-memory source file from its text.
+///
+/// ```
+/// use avjason::{common::SourceFile, lex::{tokens::Token, Lex}};
+///
+/// let src: SourceFile = "{}".parse().unwrap();
+/// assert!(Token::lex(&mut src.iter()).is_ok());
+/// ```
+///
+impl FromStr for SourceFile {
+    type Err = Infallible;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Ok(Self::dummy_file(text))
+    }
+}
+
+///
+/// A cursor which owns its source, for lexing a string straight away,
+/// without keeping a [`SourceFile`] around to borrow from.
+///
+/// Spans are char offsets into the string, as they would be in a
+/// [`SourceFile`] of the same text.
+///
+/// ```
+/// use avjason::{common::{SourceStream, Span, Spanned}, lex::tokens::Number};
+///
+/// let mut stream = SourceStream::from("42");
+/// let number: Number = stream.lex().unwrap();
+/// assert_eq!(number.span(), Span::new(0, 2));
+/// assert!(stream.is_eof());
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct SourceStream {
+    file: SourceFile,
+    position: Checkpoint,
+}
+
+impl SourceStream {
+    ///
+    /// Start a cursor at the beginning of `text`.
+    ///
+    /// As with [`SourceFile::iter`], a leading byte order mark is skipped.
+    ///
+    pub fn new(text: impl Into<String>) -> Self {
+        let file = SourceFile::dummy_file(text);
+        let position = file.iter().checkpoint();
+        Self { file, position }
+    }
+
+    ///
+    /// The anonymous file holding the text.
+    ///
+    pub fn file(&self) -> &SourceFile {
+        &self.file
+    }
+
+    ///
+    /// A borrowed cursor at the current position, for anything more
+    /// than [`SourceStream::lex`] and [`SourceStream::peek`].
+    /// Advancing it does not advance the stream.
+    ///
+    pub fn iter(&self) -> SourceIter<'_> {
+        let mut input = self.file.iter();
+        input.restore(self.position);
+        input
+    }
+
+    ///
+    /// How many characters into the text the cursor is.
+    ///
+    pub fn offset(&self) -> usize {
+        self.iter().offset()
+    }
+
+    ///
+    /// Has all of the text been consumed?
+    ///
+    pub fn is_eof(&self) -> bool {
+        self.iter().is_eof()
+    }
+
+    ///
+    /// Does the upcoming text look like the start of a `T`?
+    ///
+    pub fn peek<T: Lex>(&self) -> bool {
+        T::peek(&self.iter())
+    }
+
+    ///
+    /// Consume a `T` from the text.
+    ///
+    pub fn lex<T: Lex>(&mut self) -> Result<T, LexError> {
+        let mut input = self.iter();
+        let result = T::lex(&mut input);
+        self.position = input.checkpoint();
+        result
+    }
+}
+
+impl From<String> for SourceStream {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&str> for SourceStream {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl FromStr for SourceStream {
+    type Err = Infallible;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(text))
+    }
+}
+
+///
+/// A cursor over the characters of a [`SourceFile`].
+///
+/// Cloning (or [`SourceIter::fork`]-ing) the cursor is cheap,
+/// which lets lexers look ahead without consuming input.
+///
+#[derive(Debug, Clone)]
+pub struct SourceIter<'a> {
+    file: &'a SourceFile,
+    index: usize,
+    byte: usize,
+    options: LexOptions,
+}
+
+impl<'a> SourceIter<'a> {
+    ///
+    /// The file this cursor is reading.
+    ///
+    pub fn file(&self) -> &'a SourceFile {
+        self.file
+    }
+
+    ///
+    /// How the lexer should treat this file's contents.
+    ///
+    pub fn options(&self) -> LexOptions {
+        self.options
+    }
+
+    ///
+    /// Character offset of the next character.
+    ///
+    pub fn offset(&self) -> usize {
+        self.index
+    }
+
+    ///
+    /// Has the whole file been consumed?
+    ///
+    pub fn is_eof(&self) -> bool {
+        self.byte >= self.file.text.len()
+    }
+
+    ///
+    /// The text which has not been consumed yet.
+    ///
+    fn rest(&self) -> &'a str {
+        &self.file.text[self.byte..]
+    }
+
+    ///
+    /// Look at the next character without consuming it.
+    ///
+    pub fn peek(&self) -> Option<char> {
+        match *self.file.text.as_bytes().get(self.byte)? {
+            byte if byte.is_ascii() => Some(byte as char),
+            _ => self.rest().chars().next(),
+        }
+    }
+
+    ///
+    /// Look at the character after next without consuming anything.
+    ///
+    pub fn peek2(&self) -> Option<char> {
+        self.rest().chars().nth(1)
+    }
+
+    ///
+    /// Look `n` characters ahead without consuming anything:
+    /// `peek_n(0)` is the same as [`SourceIter::peek`].
+    ///
+    pub fn peek_n(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    ///
+    /// Are the upcoming characters exactly `s`?
+    ///
+    pub fn peek_str(&self, s: &str) -> bool {
+        self.rest().starts_with(s)
+    }
+
+    ///
+    /// Are the upcoming characters `s`, ignoring case?
+    ///
+    /// Characters are compared one at a time by their lowercase forms,
+    /// so this always matches as many characters as `s` has.
+    ///
+    pub fn peek_str_ignore_case(&self, s: &str) -> bool {
+        let mut upcoming = self.rest().chars();
+        s.chars().all(|expected| {
+            upcoming
+                .next()
+                .is_some_and(|ch| ch.to_lowercase().eq(expected.to_lowercase()))
+        })
+    }
+
+    ///
+    /// Copy this cursor, so the copy can be advanced
+    /// without affecting the original.
+    ///
+    pub fn fork(&self) -> Self {
+        self.clone()
+    }
+
+    ///
+    /// Remember the current position,
+    /// so it can be [restored](SourceIter::restore) later.
+    ///
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            index: self.index,
+            byte: self.byte,
+        }
+    }
+
+    ///
+    /// Rewind (or fast-forward) to a position saved by [`SourceIter::checkpoint`].
+    ///
+    /// `checkpoint` must have come from a cursor over the same file.
+    ///
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        self.index = checkpoint.index;
+        self.byte = checkpoint.byte;
+    }
+}
+
+///
+/// A saved position of a [`SourceIter`].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    index: usize,
+    byte: usize,
+}
+
+impl<'a> Iterator for SourceIter<'a> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ch = self.peek()?;
+        let index = self.index;
+        self.index += 1;
+        self.byte += ch.len_utf8();
+        Some((index, ch))
+    }
+}
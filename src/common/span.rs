@@ -0,0 +1,277 @@
+//!
+//! Locations of things within a source file.
+//!
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    fmt,
+    ops::{Range, RangeInclusive},
+};
+
+use super::{LineIndex, SourceFile};
+
+///
+/// A region of a source file, measured in characters.
+///
+/// The `start` offset is inclusive, and the `end` offset is exclusive,
+/// so a span with `start == end` is zero-width.
+///
+/// Lexers usually know the offset of the last character of a token,
+/// so a span can also be built from an inclusive range (`start..=end`).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    start: usize,
+    end: usize,
+}
+
+impl Span {
+    ///
+    /// Create a span covering `start..end`.
+    ///
+    pub fn new(start: usize, end: usize) -> Self {
+        debug_assert!(start <= end, "span start must not exceed its end");
+        Self { start, end }
+    }
+
+    ///
+    /// Create a zero-width span at `offset`.
+    ///
+    pub fn empty(offset: usize) -> Self {
+        Self::new(offset, offset)
+    }
+
+    ///
+    /// Offset of the first character in this span.
+    ///
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    ///
+    /// Offset one past the last character in this span.
+    ///
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    ///
+    /// Number of characters covered by this span.
+    ///
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    ///
+    /// Is this a zero-width span?
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    ///
+    /// The smallest span covering both `self` and `other`,
+    /// including any gap between them.
+    ///
+    pub fn union(&self, other: &Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+
+    ///
+    /// The smallest span covering both `self` and `other`,
+    /// like [`Span::union`] but by value.
+    ///
+    pub fn merge(self, other: Span) -> Span {
+        self.union(&other)
+    }
+
+    ///
+    /// The span from the start of `self` to the end of `other`,
+    /// or the other way round if `other` comes first.
+    ///
+    /// This is [`Span::merge`], but reads better when
+    /// building the span of a node from its first and last children:
+    /// `open.span().to(close.span())`.
+    ///
+    pub fn to(self, other: Span) -> Span {
+        self.merge(other)
+    }
+
+    ///
+    /// The characters covered by both `self` and `other`.
+    ///
+    /// Returns `None` if the spans share no characters:
+    /// this includes adjacent spans, such as `0..=1` and `2..=3`,
+    /// and zero-width spans.
+    ///
+    pub fn intersection(&self, other: &Span) -> Option<Span> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        (start < end).then(|| Span::new(start, end))
+    }
+
+    ///
+    /// This span, moved `by` characters later, or earlier if `by` is negative.
+    ///
+    /// Panics if that would move it before the start of the file.
+    ///
+    pub fn shift(self, by: isize) -> Span {
+        let shift = |offset: usize| {
+            offset
+                .checked_add_signed(by)
+                .expect("span shifted before the start of the file")
+        };
+
+        Span::new(shift(self.start), shift(self.end))
+    }
+
+    ///
+    /// Does this span cover the character at `offset`?
+    ///
+    /// The end is exclusive, so this is `start <= offset < end`,
+    /// and a zero-width span contains nothing. An editor cursor
+    /// just after a token is at its `end`, so is not inside it.
+    ///
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset < self.end
+    }
+
+    ///
+    /// Do `self` and `other` cover any of the same characters?
+    ///
+    /// This is true exactly when [`Span::intersection`] is `Some`,
+    /// so adjacent spans do not overlap.
+    ///
+    pub fn overlaps(&self, other: &Span) -> bool {
+        self.start.max(other.start) < self.end.min(other.end)
+    }
+
+    ///
+    /// Display this span as `line:column-line:column`, using `index`
+    /// to find the lines and columns.
+    ///
+    /// Lines and columns are 1-based, and the end is exclusive,
+    /// like the span itself.
+    ///
+    /// ```
+    /// use avjason::common::{SourceFile, Span};
+    ///
+    /// let src = SourceFile::dummy_file("{\n  a: 1\n}");
+    /// let span = Span::new(4, 8);
+    /// assert_eq!(span.display_with(src.line_index()).to_string(), "2:3-2:7");
+    /// ```
+    ///
+    pub fn display_with<'a>(&self, index: &'a LineIndex) -> SpanDisplay<'a> {
+        SpanDisplay { span: *self, index }
+    }
+
+    ///
+    /// The bytes of `src`'s [text](SourceFile::text) covered by this span.
+    ///
+    /// Multi-byte characters are always covered completely.
+    ///
+    pub fn byte_range(&self, src: &SourceFile) -> Range<usize> {
+        src.byte_offset(self.start)..src.byte_offset(self.end)
+    }
+}
+
+impl From<Range<usize>> for Span {
+    fn from(range: Range<usize>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl From<RangeInclusive<usize>> for Span {
+    fn from(range: RangeInclusive<usize>) -> Self {
+        Self::new(*range.start(), *range.end() + 1)
+    }
+}
+
+///
+/// A [`Span`] shown as lines and columns,
+/// see [`Span::display_with`].
+///
+#[derive(Debug, Clone, Copy)]
+pub struct SpanDisplay<'a> {
+    span: Span,
+    index: &'a LineIndex,
+}
+
+impl fmt::Display for SpanDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (start_line, start_column) = self.index.location(self.span.start);
+        let (end_line, end_column) = self.index.location(self.span.end);
+        write!(f, "{start_line}:{start_column}-{end_line}:{end_column}")
+    }
+}
+
+///
+/// Something which occupies a region of a source file.
+///
+pub trait Spanned {
+    ///
+    /// The region of the source file this occupies.
+    ///
+    fn span(&self) -> Span;
+
+    ///
+    /// The text this occupies in `src`, verbatim.
+    ///
+    /// `src` must be the file this was lexed from.
+    ///
+    fn raw<'a>(&self, src: &'a SourceFile) -> &'a str {
+        src.slice(self.span())
+    }
+}
+
+impl Spanned for Span {
+    fn span(&self) -> Span {
+        *self
+    }
+}
+
+impl<T: Spanned + ?Sized> Spanned for Box<T> {
+    fn span(&self) -> Span {
+        (**self).span()
+    }
+}
+
+///
+/// `None` has a zero-width span at the start of the file.
+///
+impl<T: Spanned> Spanned for Option<T> {
+    fn span(&self) -> Span {
+        self.as_ref().map(T::span).unwrap_or_default()
+    }
+}
+
+///
+/// The smallest span covering every element,
+/// or a zero-width span at the start of the file if there are none.
+///
+impl<T: Spanned> Spanned for [T] {
+    fn span(&self) -> Span {
+        self.iter()
+            .map(T::span)
+            .reduce(Span::merge)
+            .unwrap_or_default()
+    }
+}
+
+///
+/// The same as the span of the slice.
+///
+impl<T: Spanned, const N: usize> Spanned for [T; N] {
+    fn span(&self) -> Span {
+        self.as_slice().span()
+    }
+}
+
+///
+/// The same as the span of the slice.
+///
+impl<T: Spanned> Spanned for Vec<T> {
+    fn span(&self) -> Span {
+        self.as_slice().span()
+    }
+}
@@ -0,0 +1,474 @@
+//!
+//! Deserializing Rust types from JSON5 with [`serde`].
+//!
+//! ```
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, PartialEq, Deserialize)]
+//! struct Config {
+//!     name: String,
+//!     retries: Option<u8>,
+//! }
+//!
+//! let config: Config = avjason::de::from_str("{ name: 'avjason', /* default */ retries: null }").unwrap();
+//! assert_eq!(config, Config { name: "avjason".into(), retries: None });
+//! ```
+//!
+
+use alloc::string::{String, ToString};
+use core::{error, fmt, slice};
+
+use serde::de::{self, value::StrDeserializer, Deserialize, IntoDeserializer, Visitor};
+
+use crate::{
+    common::{SourceFile, Span, Spanned},
+    lex::tokens::LString,
+    parse::{
+        ast::{Key, Literal, Member, Value},
+        parse_ast, ParseError,
+    },
+};
+
+///
+/// Deserialize a `T` from a JSON5 document.
+///
+/// Strings and keys written without any escapes are borrowed straight from
+/// `input`, so `T` may hold `&str`s. Escaped ones have to be decoded into a
+/// new `String`, so deserializing them as a `&str` fails: use a `String`,
+/// or a `Cow<str>` with `#[serde(borrow)]`, to accept both.
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Borrowed<'a> {
+///     name: &'a str,
+/// }
+///
+/// let input = String::from("{ name: 'avjason' }");
+/// let borrowed: Borrowed = avjason::de::from_str(&input).unwrap();
+/// assert_eq!(borrowed.name, "avjason");
+/// ```
+///
+pub fn from_str<'de, T: Deserialize<'de>>(input: &'de str) -> Result<T, Error> {
+    let src = SourceFile::dummy_file(input);
+    let ast = parse_ast(&src)?;
+    T::deserialize(Deserializer {
+        src: &src,
+        text: input,
+        value: &ast,
+    })
+}
+
+///
+/// Something went wrong whilst deserializing.
+///
+/// Errors found whilst parsing, or whilst deserializing a particular value,
+/// have the span of the problem. Others, such as those from a type's own
+/// [`Deserialize`] implementation, only have a message.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    span: Option<Span>,
+    message: String,
+}
+
+impl Error {
+    ///
+    /// Where the problem is in the document, if known.
+    ///
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    ///
+    /// A description of the problem.
+    ///
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    ///
+    /// Give this error `span`, unless it already has a more specific one.
+    ///
+    fn at(mut self, span: Span) -> Self {
+        self.span.get_or_insert(span);
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.span {
+            Some(span) => write!(f, "{} at {}..{}", self.message, span.start(), span.end()),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self {
+            span: None,
+            message: msg.to_string(),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Self {
+            span: Some(err.span()),
+            message: err.message(),
+        }
+    }
+}
+
+///
+/// A [`serde::Deserializer`] for a value in an [abstract syntax tree](crate::parse::ast).
+///
+/// Unescaped strings are borrowed from `'de`,
+/// which holds the same text as the source file.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct Deserializer<'a, 'de> {
+    src: &'a SourceFile,
+    text: &'de str,
+    value: &'a Value,
+}
+
+impl<'a> Deserializer<'a, 'a> {
+    ///
+    /// Deserialize `value`, which was parsed from `src`.
+    ///
+    pub fn new(src: &'a SourceFile, value: &'a Value) -> Self {
+        Self {
+            src,
+            text: src.text(),
+            value,
+        }
+    }
+}
+
+impl<'a, 'de> Deserializer<'a, 'de> {
+    fn nested(&self, value: &'a Value) -> Self {
+        Self { value, ..*self }
+    }
+
+    ///
+    /// The contents of `string`, if it has no escapes to decode.
+    ///
+    fn borrow_string(&self, string: &LString) -> Option<&'de str> {
+        let bytes = string.span().byte_range(self.src);
+        // Both quotes are one byte long.
+        let contents = &self.text[bytes.start + 1..bytes.end - 1];
+        (!contents.contains('\\')).then_some(contents)
+    }
+
+    ///
+    /// The name of a member's `key`, with the lifetime of the input
+    /// if it has no escapes to decode.
+    ///
+    fn key(&self, member: &'a Member) -> KeyDeserializer<'a, 'de> {
+        let borrowed = match &member.key {
+            Key::Identifier(ident) => {
+                let raw = &self.text[ident.span().byte_range(self.src)];
+                (!raw.contains('\\')).then_some(raw)
+            }
+            Key::String(string) => self.borrow_string(string),
+        };
+
+        KeyDeserializer {
+            name: &member.name,
+            borrowed,
+        }
+    }
+}
+
+///
+/// Deserialize methods for integers, which take `-0` as zero,
+/// although [`deserialize_any`](de::Deserializer::deserialize_any)
+/// gives it as a float to keep its sign.
+///
+macro_rules! deserialize_integers {
+    ($($method:ident)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.value {
+                Value::Literal(Literal::Number { value, .. }) if is_negative_zero(*value) => {
+                    visitor
+                        .visit_u64(0)
+                        .map_err(|err: Error| err.at(self.value.span()))
+                }
+                _ => self.deserialize_any(visitor),
+            }
+        }
+    )*};
+}
+
+impl<'a, 'de> de::Deserializer<'de> for Deserializer<'a, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let result = match self.value {
+            Value::Object(object) => visitor.visit_map(MapAccess {
+                de: self,
+                members: object.members.iter(),
+                value: None,
+            }),
+            Value::Array(array) => visitor.visit_seq(SeqAccess {
+                de: self,
+                elements: array.elements.iter(),
+            }),
+            Value::Literal(Literal::Null(_)) => visitor.visit_unit(),
+            Value::Literal(Literal::Bool { value, .. }) => visitor.visit_bool(*value),
+            Value::Literal(Literal::String { token, value }) => match self.borrow_string(token) {
+                Some(borrowed) => visitor.visit_borrowed_str(borrowed),
+                None => visitor.visit_str(value),
+            },
+            Value::Literal(Literal::Number { token, value }) => {
+                // `-0` is an integer, but only a float can keep its sign.
+                if is_negative_zero(*value) {
+                    visitor.visit_f64(*value)
+                } else if let Some(n) = token.as_u64(self.src) {
+                    visitor.visit_u64(n)
+                } else if let Some(n) = token.as_i64(self.src) {
+                    visitor.visit_i64(n)
+                } else {
+                    visitor.visit_f64(*value)
+                }
+            }
+        };
+
+        result.map_err(|err| err.at(self.value.span()))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Value::Literal(Literal::Null(_)) => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    ///
+    /// Unit variants are written as strings, as in `'Red'`, and other
+    /// variants as an object with a single key, as in `{ Rgb: [0, 0, 0] }`.
+    ///
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let result = match self.value {
+            Value::Literal(Literal::String { value, .. }) => {
+                visitor.visit_enum(value.as_str().into_deserializer())
+            }
+            Value::Object(object) if object.members.len() == 1 => visitor.visit_enum(EnumAccess {
+                de: self,
+                member: &object.members[0],
+            }),
+            _ => Err(de::Error::custom(
+                "expected an enum variant, written as a string or an object with a single key",
+            )),
+        };
+
+        result.map_err(|err| err.at(self.value.span()))
+    }
+
+    deserialize_integers! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool f32 f64 char str string bytes byte_buf unit unit_struct
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+fn is_negative_zero(n: f64) -> bool {
+    n == 0.0 && n.is_sign_negative()
+}
+
+struct SeqAccess<'a, 'de> {
+    de: Deserializer<'a, 'de>,
+    elements: slice::Iter<'a, Value>,
+}
+
+impl<'a, 'de> de::SeqAccess<'de> for SeqAccess<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.elements
+            .next()
+            .map(|element| seed.deserialize(self.de.nested(element)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
+
+struct MapAccess<'a, 'de> {
+    de: Deserializer<'a, 'de>,
+    members: slice::Iter<'a, Member>,
+    value: Option<&'a Value>,
+}
+
+impl<'a, 'de> de::MapAccess<'de> for MapAccess<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some(member) = self.members.next() else {
+            return Ok(None);
+        };
+
+        self.value = Some(&member.value);
+        seed.deserialize(self.de.key(member))
+            .map(Some)
+            .map_err(|err| err.at(member.key.span()))
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .expect("`next_value_seed` is called after `next_key_seed`");
+        seed.deserialize(self.de.nested(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.members.len())
+    }
+}
+
+///
+/// Deserializes an object key, which is always a string, but may also
+/// be read as an integer, since that is how [`ser`](crate::ser) writes
+/// integer map keys.
+///
+struct KeyDeserializer<'a, 'de> {
+    name: &'a str,
+    borrowed: Option<&'de str>,
+}
+
+macro_rules! deserialize_integer_key {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                match self.name.parse() {
+                    Ok(n) => visitor.$visit(n),
+                    Err(_) => de::Deserializer::deserialize_any(self, visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer<'_, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.borrowed {
+            Some(borrowed) => visitor.visit_borrowed_str(borrowed),
+            None => visitor.visit_str(self.name),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let key: StrDeserializer<Error> = self.name.into_deserializer();
+        visitor.visit_enum(key)
+    }
+
+    deserialize_integer_key! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool f32 f64 char str string bytes byte_buf option unit unit_struct
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct EnumAccess<'a, 'de> {
+    de: Deserializer<'a, 'de>,
+    member: &'a Member,
+}
+
+impl<'a, 'de> de::EnumAccess<'de> for EnumAccess<'a, 'de> {
+    type Error = Error;
+    type Variant = Deserializer<'a, 'de>;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Error> {
+        let variant = seed
+            .deserialize(self.de.key(self.member))
+            .map_err(|err| err.at(self.member.key.span()))?;
+        Ok((variant, self.de.nested(&self.member.value)))
+    }
+}
+
+impl<'a, 'de> de::VariantAccess<'de> for Deserializer<'a, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        <()>::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
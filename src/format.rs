@@ -0,0 +1,495 @@
+//!
+//! Writing [`Value`]s back out as JSON5 text,
+//! and reformatting documents without losing their comments.
+//!
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{cmp::Ordering, mem};
+
+use crate::{
+    common::SourceFile,
+    lex::{escapes::escape, identifier::LIdentifier, strings::QuoteStyle},
+    parse::{
+        cst::{Cst, LeafKind, Node},
+        parse_cst, ParseError,
+    },
+    value::Value,
+};
+
+///
+/// Options for how [`to_string_pretty`] and [`to_string_compact_with`]
+/// write a value.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    ///
+    /// How many spaces to indent each level of nesting by,
+    /// when pretty-printing.
+    ///
+    /// Defaults to 2.
+    ///
+    pub indent: usize,
+
+    ///
+    /// Whether to quote every key, as JSON does. Otherwise, keys are
+    /// only quoted if they are not [identifier names](LIdentifier::is_identifier_name).
+    ///
+    /// Defaults to `false`.
+    ///
+    pub quote_keys: bool,
+
+    ///
+    /// The quotes to write strings, and quoted keys, in.
+    ///
+    /// Defaults to double quotes.
+    ///
+    pub quote: QuoteStyle,
+
+    ///
+    /// Whether to write each string in whichever quotes need fewer
+    /// escapes, as in `'say "hi"'` and `"it's"`, only falling back to
+    /// [`quote`](FormatOptions::quote) when it makes no difference.
+    ///
+    /// Defaults to `false`.
+    ///
+    pub shortest_quotes: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            quote_keys: false,
+            quote: QuoteStyle::Double,
+            shortest_quotes: false,
+        }
+    }
+}
+
+///
+/// Write `value` as JSON5, with each array element and object member
+/// on its own line. Empty arrays and objects stay on one line.
+///
+/// ```
+/// use avjason::{
+///     common::SourceFile,
+///     format::{to_string_pretty, FormatOptions},
+///     parse::parse,
+/// };
+///
+/// let value = parse(&SourceFile::dummy_file("{ a: [1, 2], 'b c': {} }")).unwrap();
+/// assert_eq!(
+///     to_string_pretty(&value, &FormatOptions::default()),
+///     "{\n  a: [\n    1,\n    2\n  ],\n  \"b c\": {}\n}",
+/// );
+/// ```
+///
+pub fn to_string_pretty(value: &Value, options: &FormatOptions) -> String {
+    Writer::write(value, options, true)
+}
+
+///
+/// Write `value` as the smallest JSON5 it can be, with the default options:
+/// no whitespace, keys unquoted wherever they can be,
+/// and the shortest form of each number.
+///
+/// ```
+/// use avjason::{common::SourceFile, format::to_string_compact, parse::parse};
+///
+/// let value = parse(&SourceFile::dummy_file("{ 'a': [0.5, 1000000], b: {} }")).unwrap();
+/// assert_eq!(to_string_compact(&value), "{a:[.5,1e6],b:{}}");
+/// ```
+///
+pub fn to_string_compact(value: &Value) -> String {
+    to_string_compact_with(value, &FormatOptions::default())
+}
+
+///
+/// Write `value` as compactly as [`to_string_compact`], using `options`
+/// for quotes. The indent is ignored.
+///
+pub fn to_string_compact_with(value: &Value, options: &FormatOptions) -> String {
+    Writer::write(value, options, false)
+}
+
+struct Writer<'a> {
+    out: String,
+    options: &'a FormatOptions,
+    // Whether to break lists onto multiple lines, or leave out all whitespace.
+    pretty: bool,
+    depth: usize,
+}
+
+impl<'a> Writer<'a> {
+    fn write(value: &Value, options: &'a FormatOptions, pretty: bool) -> String {
+        let mut writer = Self {
+            out: String::new(),
+            options,
+            pretty,
+            depth: 0,
+        };
+        writer.value(value);
+        writer.out
+    }
+
+    fn value(&mut self, value: &Value) {
+        match value {
+            Value::Null => self.out.push_str("null"),
+            Value::Bool(b) => self.out.push_str(if *b { "true" } else { "false" }),
+            Value::Number(n) => self.out.push_str(&number(*n, !self.pretty)),
+            Value::String(s) => self.string(s),
+            Value::Array(items) => self.list('[', ']', items, |writer, item| writer.value(item)),
+            Value::Object(members) => self.list('{', '}', members, |writer, (key, value)| {
+                writer.key(key);
+                writer.out.push_str(if writer.pretty { ": " } else { ":" });
+                writer.value(value);
+            }),
+        }
+    }
+
+    fn string(&mut self, text: &str) {
+        let count = |quote: QuoteStyle| text.chars().filter(|&ch| ch == quote.as_char()).count();
+        let quote = match self.options.shortest_quotes {
+            // Each quote inside the string needs escaping if it matches.
+            true => match count(QuoteStyle::Single).cmp(&count(QuoteStyle::Double)) {
+                Ordering::Less => QuoteStyle::Single,
+                Ordering::Greater => QuoteStyle::Double,
+                Ordering::Equal => self.options.quote,
+            },
+            false => self.options.quote,
+        };
+
+        self.out.push(quote.as_char());
+        self.out.push_str(&escape(text, quote));
+        self.out.push(quote.as_char());
+    }
+
+    fn key(&mut self, key: &str) {
+        match !self.options.quote_keys && LIdentifier::is_identifier_name(key) {
+            true => self.out.push_str(key),
+            false => self.string(key),
+        }
+    }
+
+    fn newline(&mut self) {
+        if !self.pretty {
+            return;
+        }
+
+        self.out.push('\n');
+        let width = self.depth * self.options.indent;
+        self.out.extend(core::iter::repeat_n(' ', width));
+    }
+
+    ///
+    /// Write `items` between `open` and `close`, one per line if pretty-printing.
+    ///
+    fn list<I: IntoIterator>(
+        &mut self,
+        open: char,
+        close: char,
+        items: I,
+        mut item: impl FnMut(&mut Self, I::Item),
+    ) {
+        self.out.push(open);
+        let mut items = items.into_iter().peekable();
+        if items.peek().is_none() {
+            self.out.push(close);
+            return;
+        }
+
+        self.depth += 1;
+        while let Some(next) = items.next() {
+            self.newline();
+            item(self, next);
+            if items.peek().is_some() {
+                self.out.push(',');
+            }
+        }
+        self.depth -= 1;
+
+        self.newline();
+        self.out.push(close);
+    }
+}
+
+///
+/// Write `n` as a JSON5 number literal.
+///
+/// If `compact`, this also leaves out any leading zero, as in `.5`,
+/// and uses an exponent wherever that is shorter, as in `1e6`.
+/// Otherwise, numbers are written out in full, as people would write them.
+///
+fn number(n: f64, compact: bool) -> String {
+    if n.is_nan() {
+        return "NaN".into();
+    }
+
+    if n.is_infinite() {
+        return if n > 0.0 { "Infinity" } else { "-Infinity" }.into();
+    }
+
+    let mut plain = n.to_string();
+    if !compact {
+        return plain;
+    }
+
+    if let Some(fraction) = plain.strip_prefix("0.") {
+        plain = format!(".{fraction}");
+    } else if let Some(fraction) = plain.strip_prefix("-0.") {
+        plain = format!("-.{fraction}");
+    }
+
+    // Both are the shortest digits which read back as `n`, but very large
+    // or very small numbers are shorter with an exponent: `1e300`.
+    let exponent = format!("{n:e}");
+    match exponent.len() < plain.len() {
+        true => exponent,
+        false => plain,
+    }
+}
+
+///
+/// Reprint a document with each array element and object member on its
+/// own line, indented by `options.indent`, keeping its comments.
+///
+/// Keys and values keep the text they were written with, so a string
+/// keeps its quotes, and a number its form: `options` only sets the indent.
+/// Trailing commas are dropped, and at most one blank line is kept
+/// wherever there were blank lines between elements or comments.
+///
+/// Comments stay next to the element or member they were written by:
+///
+/// * a comment on the same line as an element (or the comma after it)
+///   stays at the end of that line;
+/// * a comment on a line of its own stays on its own line,
+///   before the next element;
+/// * a comment on its own line after the last element, or in an array or
+///   object with no elements, is *dangling*: it stays on its own line,
+///   before the closing bracket, so `{ /* empty */ }` is broken onto
+///   three lines;
+/// * a comment between a key and its value is moved before the member.
+///
+/// ```
+/// use avjason::{common::SourceFile, format::{reformat, FormatOptions}};
+///
+/// let src = SourceFile::dummy_file("{a: 1, // one\n  b: [ /* none */ ]}");
+/// assert_eq!(
+///     reformat(&src, &FormatOptions::default()).unwrap(),
+///     "{\n  a: 1, // one\n  b: [\n    /* none */\n  ]\n}\n",
+/// );
+/// ```
+///
+pub fn reformat(src: &SourceFile, options: &FormatOptions) -> Result<String, ParseError> {
+    parse_cst(src).map(|cst| format_cst(&cst, options))
+}
+
+///
+/// Reprint a parsed document, as [`reformat`] does.
+///
+pub fn format_cst(cst: &Cst, options: &FormatOptions) -> String {
+    let mut reformatter = Reformatter {
+        out: String::new(),
+        options,
+        depth: 0,
+    };
+    reformatter.items(&Items::split(cst.children()), true);
+    reformatter.out.push('\n');
+    reformatter.out
+}
+
+fn is_trivia(node: &Node) -> bool {
+    matches!(node, Node::Leaf(leaf) if leaf.kind().is_trivia())
+}
+
+fn comment(node: &Node) -> Option<&str> {
+    match node {
+        Node::Leaf(leaf) if leaf.kind() == LeafKind::Comment => Some(leaf.text()),
+        _ => None,
+    }
+}
+
+///
+/// A comment on a line of its own.
+///
+struct Comment<'a> {
+    // Whether there was a blank line before it.
+    blank_before: bool,
+    text: &'a str,
+}
+
+///
+/// An element or member, and the comments which belong to it.
+///
+struct Item<'a> {
+    leading: Vec<Comment<'a>>,
+    blank_before: bool,
+    node: &'a Node,
+    trailing: Vec<&'a str>,
+}
+
+///
+/// The items between a pair of brackets, or the value of a whole document.
+///
+struct Items<'a> {
+    items: Vec<Item<'a>>,
+    dangling: Vec<Comment<'a>>,
+}
+
+impl<'a> Items<'a> {
+    ///
+    /// Share out the comments among `nodes`, which do not include brackets.
+    ///
+    fn split(nodes: &'a [Node]) -> Self {
+        let mut items: Vec<Item> = vec![];
+        let mut pending = vec![];
+        // Line breaks since the last item or comment.
+        let mut line_breaks = 0;
+
+        for node in nodes {
+            match node {
+                Node::Leaf(leaf) if leaf.kind() == LeafKind::LineTerminator => line_breaks += 1,
+                Node::Leaf(leaf) if leaf.kind() == LeafKind::Comment => {
+                    match items.last_mut() {
+                        Some(item) if line_breaks == 0 && pending.is_empty() => {
+                            item.trailing.push(leaf.text())
+                        }
+                        _ => pending.push(Comment {
+                            blank_before: line_breaks > 1,
+                            text: leaf.text(),
+                        }),
+                    }
+                    line_breaks = 0;
+                }
+                // Whitespace and commas are written afresh.
+                Node::Leaf(leaf)
+                    if matches!(leaf.kind(), LeafKind::WhiteSpace | LeafKind::Punctuator) => {}
+                _ => {
+                    let mut leading = mem::take(&mut pending);
+                    if let Node::Member(children) = node {
+                        leading.extend(children.iter().filter_map(comment).map(|text| Comment {
+                            blank_before: false,
+                            text,
+                        }));
+                    }
+
+                    items.push(Item {
+                        leading,
+                        blank_before: line_breaks > 1,
+                        node,
+                        trailing: vec![],
+                    });
+                    line_breaks = 0;
+                }
+            }
+        }
+
+        Self {
+            items,
+            dangling: pending,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.items.is_empty() && self.dangling.is_empty()
+    }
+}
+
+struct Reformatter<'a> {
+    out: String,
+    options: &'a FormatOptions,
+    depth: usize,
+}
+
+impl Reformatter<'_> {
+    fn node(&mut self, node: &Node) {
+        match node {
+            Node::Leaf(leaf) => self.out.push_str(leaf.text()),
+            Node::Object(children) => self.brackets(children),
+            Node::Array(children) => self.brackets(children),
+            Node::Member(children) => {
+                let mut parts = children.iter().filter(|node| !is_trivia(node));
+                let key = parts.next().expect("a member has a key");
+                let value = parts.next_back().expect("a member has a value");
+                self.node(key);
+                self.out.push_str(": ");
+                self.node(value);
+            }
+        }
+    }
+
+    ///
+    /// Write an array or object, whose first and last children are its brackets.
+    ///
+    fn brackets(&mut self, children: &[Node]) {
+        let [open, inner @ .., close] = children else {
+            unreachable!("arrays and objects have both brackets");
+        };
+
+        self.node(open);
+        let items = Items::split(inner);
+        if !items.is_empty() {
+            self.depth += 1;
+            self.items(&items, false);
+            self.depth -= 1;
+            self.line(false);
+        }
+        self.node(close);
+    }
+
+    ///
+    /// Start a new line, at the current depth.
+    ///
+    fn line(&mut self, blank_before: bool) {
+        self.out.push('\n');
+        if blank_before {
+            self.out.push('\n');
+        }
+
+        let width = self.depth * self.options.indent;
+        self.out.extend(core::iter::repeat_n(' ', width));
+    }
+
+    ///
+    /// Write `items`, one per line, with commas between them.
+    ///
+    /// At the `top` level, there is only one item, and nothing before it.
+    ///
+    fn items(&mut self, items: &Items, top: bool) {
+        let mut first = true;
+        let mut line = |this: &mut Self, blank_before: bool| {
+            if !(top && first) {
+                this.line(blank_before && !first);
+            }
+            first = false;
+        };
+
+        for (i, item) in items.items.iter().enumerate() {
+            for comment in &item.leading {
+                line(self, comment.blank_before);
+                self.out.push_str(comment.text);
+            }
+
+            line(self, item.blank_before);
+            self.node(item.node);
+            if i + 1 < items.items.len() {
+                self.out.push(',');
+            }
+
+            for comment in &item.trailing {
+                self.out.push(' ');
+                self.out.push_str(comment);
+            }
+        }
+
+        for comment in &items.dangling {
+            line(self, comment.blank_before);
+            self.out.push_str(comment.text);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//!
+//! Deduplication of identifier names.
+//!
+//! Large documents tend to repeat the same object keys over and over.
+//! An [`Interner`] gives each distinct name a small [`Symbol`],
+//! which is cheap to copy, compare, and hash.
+//!
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{collections::HashMap, common::SourceFile, lex::identifier::LIdentifier};
+
+///
+/// A name which has been [interned](Interner::intern).
+///
+/// Symbols are only meaningful to the [`Interner`] which made them.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+///
+/// Hands out one [`Symbol`] per distinct identifier name.
+///
+#[derive(Debug, Default)]
+pub struct Interner {
+    symbols: HashMap<Box<str>, Symbol>,
+    names: Vec<Box<str>>,
+}
+
+impl Interner {
+    ///
+    /// An interner with no names in it yet.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// The symbol for `id`'s [name](LIdentifier::as_str),
+    /// so differently-escaped spellings of the same name
+    /// get the same symbol.
+    ///
+    pub fn intern(&mut self, id: &LIdentifier, src: &SourceFile) -> Symbol {
+        self.intern_str(id.as_str(src))
+    }
+
+    ///
+    /// The symbol for `name`.
+    ///
+    pub fn intern_str(&mut self, name: &str) -> Symbol {
+        if let Some(&symbol) = self.symbols.get(name) {
+            return symbol;
+        }
+
+        let symbol = Symbol(
+            self.names
+                .len()
+                .try_into()
+                .expect("too many distinct names to intern"),
+        );
+        self.names.push(name.into());
+        self.symbols.insert(name.into(), symbol);
+        symbol
+    }
+
+    ///
+    /// The name `symbol` was interned from.
+    ///
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.names[symbol.0 as usize]
+    }
+
+    ///
+    /// The number of distinct names interned so far.
+    ///
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    ///
+    /// Has nothing been interned yet?
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
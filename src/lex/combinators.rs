@@ -0,0 +1,379 @@
+//!
+//! Generic tokens built out of other tokens.
+//!
+
+use alloc::{vec, vec::Vec};
+use core::{array, ops::Deref};
+
+use crate::common::{SourceIter, Span, Spanned};
+
+use super::{Lex, LexError};
+
+///
+/// Exactly `N` of the token `T`, one after another.
+///
+/// The tokens are kept in an array, so lexing them never allocates.
+/// Derefs to a slice of the tokens.
+///
+/// `Exactly<0, T>` always lexes, without consuming anything,
+/// and has an empty span where the tokens would have started.
+///
+/// ```
+/// use avjason::{
+///     common::{SourceFile, Span, Spanned},
+///     lex::{combinators::Exactly, digits::HexDigit, Lex},
+/// };
+///
+/// let src = SourceFile::dummy_file("c0ffee");
+/// let digits = Exactly::<4, HexDigit>::lex(&mut src.iter()).unwrap();
+/// assert_eq!(digits.span(), Span::new(0, 4));
+/// assert_eq!(digits[1].value(), 0);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct Exactly<const N: usize, T> {
+    span: Span,
+    items: [T; N],
+}
+
+impl<const N: usize, T> Exactly<N, T> {
+    ///
+    /// The tokens, as an array.
+    ///
+    pub fn as_array(&self) -> &[T; N] {
+        &self.items
+    }
+
+    ///
+    /// Take the tokens out.
+    ///
+    pub fn into_inner(self) -> [T; N] {
+        self.items
+    }
+}
+
+impl<const N: usize, T> Deref for Exactly<N, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<const N: usize, T: Lex> Lex for Exactly<N, T> {
+    fn peek(input: &SourceIter) -> bool {
+        N == 0 || T::peek(input)
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let start = input.offset();
+
+        let mut items: [Option<T>; N] = array::from_fn(|_| None);
+        for item in &mut items {
+            *item = Some(T::lex(input)?);
+        }
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+            items: items.map(|item| item.expect("lexed every item")),
+        })
+    }
+}
+
+///
+/// Zero or more of the token `T`, for as long as the input looks like one.
+///
+/// Derefs to a slice of the tokens. With none, the span is empty,
+/// where they would have started.
+///
+/// ```
+/// use avjason::{
+///     common::{SourceFile, Span, Spanned},
+///     lex::{combinators::Many, digits::HexDigit, Lex},
+/// };
+///
+/// let src = SourceFile::dummy_file("c0ffee!");
+/// let digits = Many::<HexDigit>::lex(&mut src.iter()).unwrap();
+/// assert_eq!(digits.span(), Span::new(0, 6));
+/// assert_eq!(digits.len(), 6);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct Many<T> {
+    span: Span,
+    items: Vec<T>,
+}
+
+impl<T> Many<T> {
+    ///
+    /// Take the tokens out.
+    ///
+    pub fn into_inner(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T> Deref for Many<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T: Lex> Lex for Many<T> {
+    fn peek(_: &SourceIter) -> bool {
+        // There can always be none.
+        true
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let start = input.offset();
+
+        let mut items = vec![];
+        lex_while(input, &mut items)?;
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+            items,
+        })
+    }
+}
+
+///
+/// At least `N` of the token `T`, and then as many more as there are.
+///
+/// Derefs to a slice of the tokens.
+///
+/// ```
+/// use avjason::{
+///     common::{SourceFile, Span, Spanned},
+///     lex::{combinators::AtLeast, digits::HexDigit, Lex},
+/// };
+///
+/// let src = SourceFile::dummy_file("c0ffee");
+/// let digits = AtLeast::<2, HexDigit>::lex(&mut src.iter()).unwrap();
+/// assert_eq!(digits.span(), Span::new(0, 6));
+///
+/// let src = SourceFile::dummy_file("c!");
+/// assert!(AtLeast::<2, HexDigit>::lex(&mut src.iter()).is_err());
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct AtLeast<const N: usize, T> {
+    span: Span,
+    items: Vec<T>,
+}
+
+impl<const N: usize, T> AtLeast<N, T> {
+    ///
+    /// Take the tokens out. There are always at least `N`.
+    ///
+    pub fn into_inner(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<const N: usize, T> Deref for AtLeast<N, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<const N: usize, T: Lex> Lex for AtLeast<N, T> {
+    fn peek(input: &SourceIter) -> bool {
+        N == 0 || T::peek(input)
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let start = input.offset();
+
+        let mut items = Vec::with_capacity(N);
+        for _ in 0..N {
+            items.push(T::lex(input)?);
+        }
+        lex_while(input, &mut items)?;
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+            items,
+        })
+    }
+}
+
+///
+/// The token `T`, if the input looks like one, or nothing.
+///
+/// When there is nothing, the span is empty, where it would have been.
+///
+/// ```
+/// use avjason::{
+///     common::{SourceFile, Span, Spanned},
+///     lex::{combinators::Optional, Lex},
+///     Token,
+/// };
+///
+/// let src = SourceFile::dummy_file("1");
+/// let sign = Optional::<Token![+]>::lex(&mut src.iter()).unwrap();
+/// assert!(sign.as_ref().is_none());
+/// assert_eq!(sign.span(), Span::empty(0));
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct Optional<T> {
+    span: Span,
+    item: Option<T>,
+}
+
+impl<T> Optional<T> {
+    ///
+    /// The token, if there was one.
+    ///
+    pub fn as_ref(&self) -> Option<&T> {
+        self.item.as_ref()
+    }
+
+    ///
+    /// Take the token out, if there was one.
+    ///
+    pub fn into_inner(self) -> Option<T> {
+        self.item
+    }
+}
+
+impl<T: Lex> Lex for Optional<T> {
+    fn peek(_: &SourceIter) -> bool {
+        // There can always be nothing.
+        true
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let start = input.offset();
+        let item = match T::peek(input) {
+            true => Some(T::lex(input)?),
+            false => None,
+        };
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+            item,
+        })
+    }
+}
+
+///
+/// One or more of the token `T`, with a `Sep` between each,
+/// as in `a,b,c` for `Separated<Ident, Comma>`.
+///
+/// If `TRAILING` is `true`, there may also be a separator after the last
+/// token. Otherwise, a separator which is not followed by a `T` is left
+/// in the input, after the end of the list.
+///
+/// Derefs to a slice of the tokens, without the separators.
+///
+/// ```
+/// use avjason::{
+///     common::{SourceFile, Span, Spanned},
+///     lex::{combinators::Separated, digits::HexDigit, tokens::Comma, Lex},
+/// };
+///
+/// let src = SourceFile::dummy_file("a,b,c,");
+/// let list = Separated::<HexDigit, Comma, true>::lex(&mut src.iter()).unwrap();
+/// assert_eq!(list.len(), 3);
+/// assert_eq!(list.separators().len(), 3);
+/// assert_eq!(list.span(), Span::new(0, 6));
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct Separated<T, Sep, const TRAILING: bool = false> {
+    span: Span,
+    items: Vec<T>,
+    separators: Vec<Sep>,
+}
+
+impl<T, Sep, const TRAILING: bool> Separated<T, Sep, TRAILING> {
+    ///
+    /// The separators, in order. The `i`th one comes after the `i`th token.
+    ///
+    pub fn separators(&self) -> &[Sep] {
+        &self.separators
+    }
+
+    ///
+    /// The separator after the last token, if there is one.
+    ///
+    pub fn trailing(&self) -> Option<&Sep> {
+        match self.separators.len() == self.items.len() {
+            true => self.separators.last(),
+            false => None,
+        }
+    }
+
+    ///
+    /// Take the tokens and separators out.
+    ///
+    pub fn into_inner(self) -> (Vec<T>, Vec<Sep>) {
+        (self.items, self.separators)
+    }
+}
+
+impl<T, Sep, const TRAILING: bool> Deref for Separated<T, Sep, TRAILING> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T: Lex, Sep: Lex, const TRAILING: bool> Lex for Separated<T, Sep, TRAILING> {
+    fn peek(input: &SourceIter) -> bool {
+        T::peek(input)
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let start = input.offset();
+
+        let mut items = vec![T::lex(input)?];
+        let mut separators = vec![];
+        while Sep::peek(input) {
+            let before = input.checkpoint();
+            let separator = Sep::lex(input)?;
+            if T::peek(input) {
+                separators.push(separator);
+                items.push(T::lex(input)?);
+                continue;
+            }
+
+            match TRAILING {
+                true => separators.push(separator),
+                false => input.restore(before),
+            }
+            break;
+        }
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+            items,
+            separators,
+        })
+    }
+}
+
+///
+/// Lex `T`s onto the end of `items` for as long as the input looks like one.
+///
+fn lex_while<T: Lex>(input: &mut SourceIter, items: &mut Vec<T>) -> Result<(), LexError> {
+    while T::peek(input) {
+        let before = input.offset();
+        items.push(T::lex(input)?);
+
+        // A `T` which can be empty would otherwise repeat forever.
+        if input.offset() == before {
+            break;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+//!
+//! Comments.
+//!
+
+use crate::common::{SourceFile, SourceIter, Span, Spanned};
+
+use super::{expect, incremental::Shift, line_terminator::is_line_terminator, Lex, LexError};
+
+///
+/// A [Comment](https://262.ecma-international.org/5.1/#sec-7.4).
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned, Lex)]
+pub enum Comment {
+    SingleLine(SingleLineComment),
+    MultiLine(MultiLineComment),
+}
+
+impl Shift for Comment {
+    fn shift(&mut self, by: isize) {
+        match self {
+            Self::SingleLine(comment) => comment.shift(by),
+            Self::MultiLine(comment) => comment.shift(by),
+        }
+    }
+}
+
+impl Comment {
+    ///
+    /// The body of this comment, without its delimiters.
+    ///
+    pub fn text<'a>(&self, src: &'a SourceFile) -> &'a str {
+        match self {
+            Self::SingleLine(comment) => comment.text(src),
+            Self::MultiLine(comment) => comment.text(src),
+        }
+    }
+}
+
+///
+/// A `// comment`, running until the end of the line.
+///
+/// The line terminator is not part of the comment.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct SingleLineComment {
+    span: Span,
+}
+
+impl SingleLineComment {
+    ///
+    /// The body of this comment, after the `//`.
+    ///
+    pub fn text<'a>(&self, src: &'a SourceFile) -> &'a str {
+        &self.raw(src)[2..]
+    }
+}
+
+impl Shift for SingleLineComment {
+    fn shift(&mut self, by: isize) {
+        self.span = self.span.shift(by);
+    }
+}
+
+impl Lex for SingleLineComment {
+    fn peek(input: &SourceIter) -> bool {
+        input.peek() == Some('/') && input.peek2() == Some('/')
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let start = expect(input, '/')?;
+        expect(input, '/')?;
+
+        while input.peek().is_some_and(|ch| !is_line_terminator(ch)) {
+            input.next();
+        }
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+        })
+    }
+}
+
+///
+/// A `/* comment */`, which may span multiple lines.
+///
+/// By default, these do not nest: the comment ends at the first `*/`.
+/// With [`LexOptions::nested_comments`](super::LexOptions::nested_comments),
+/// each `/*` inside the comment must be closed by its own `*/`.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct MultiLineComment {
+    span: Span,
+}
+
+impl MultiLineComment {
+    ///
+    /// The body of this comment, between the `/*` and `*/`.
+    ///
+    /// With [nested comments](super::LexOptions::nested_comments),
+    /// this includes the delimiters of any inner comments.
+    ///
+    pub fn text<'a>(&self, src: &'a SourceFile) -> &'a str {
+        let raw = self.raw(src);
+        &raw[2..raw.len() - 2]
+    }
+
+    fn peek_end(input: &SourceIter) -> bool {
+        input.peek() == Some('*') && input.peek2() == Some('/')
+    }
+}
+
+impl Shift for MultiLineComment {
+    fn shift(&mut self, by: isize) {
+        self.span = self.span.shift(by);
+    }
+}
+
+impl Lex for MultiLineComment {
+    fn peek(input: &SourceIter) -> bool {
+        input.peek() == Some('/') && input.peek2() == Some('*')
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let start = expect(input, '/')?;
+        expect(input, '*')?;
+
+        let nested = input.options().nested_comments;
+        let mut depth = 1;
+        while depth > 0 {
+            if Self::peek_end(input) {
+                depth -= 1;
+            } else if nested && Self::peek(input) {
+                depth += 1;
+            } else if input.next().is_some() {
+                continue;
+            } else {
+                return Err(LexError::UnterminatedComment {
+                    span: Span::new(start, input.offset()),
+                });
+            }
+
+            input.next();
+            input.next();
+        }
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+        })
+    }
+}
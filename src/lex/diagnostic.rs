@@ -0,0 +1,123 @@
+//!
+//! Reporting [`LexError`]s through [`miette`].
+//!
+
+use std::{error::Error, fmt, iter};
+
+use miette::{Diagnostic, LabeledSpan, NamedSource, SourceSpan};
+
+use crate::common::{SourceFile, Spanned};
+
+use super::LexError;
+
+///
+/// A [`LexError`], along with the source file it came from,
+/// so it can be shown in context by [`miette`].
+///
+/// ```
+/// use avjason::{common::SourceFile, lex::{strings::LString, Lex}};
+///
+/// let src = SourceFile::dummy_file("'abc");
+/// let error = LString::lex(&mut src.iter()).unwrap_err();
+/// let report = miette::Report::new(error.into_diagnostic(&src));
+/// ```
+///
+/// A [`LexError`] also converts straight into one with `?`, without its source:
+///
+/// ```
+/// use avjason::{
+///     common::SourceFile,
+///     lex::{strings::LString, Lex, LexDiagnostic},
+/// };
+///
+/// fn lex(src: &SourceFile) -> Result<LString, LexDiagnostic> {
+///     Ok(LString::lex(&mut src.iter())?)
+/// }
+///
+/// assert!(lex(&SourceFile::dummy_file("'abc")).is_err());
+/// ```
+///
+#[derive(Debug)]
+pub struct LexDiagnostic {
+    error: LexError,
+    // Boxed, so a `Result<_, LexDiagnostic>` stays small.
+    source: Option<Box<NamedSource<String>>>,
+    span: SourceSpan,
+}
+
+impl LexDiagnostic {
+    pub fn new(error: LexError, src: &SourceFile) -> Self {
+        let bytes = error.span().byte_range(src);
+        Self {
+            source: Some(Box::new(NamedSource::new(
+                src.path().display().to_string(),
+                src.text().to_owned(),
+            ))),
+            span: bytes.into(),
+            error,
+        }
+    }
+
+    ///
+    /// The error being reported.
+    ///
+    pub fn error(&self) -> &LexError {
+        &self.error
+    }
+
+    ///
+    /// Where the error is, in bytes, as [`miette`] counts them.
+    ///
+    /// Without the source, there is no way to tell where its multi-byte
+    /// characters are, so this is the error's span in chars,
+    /// which is only right for ASCII text.
+    ///
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+}
+
+impl LexError {
+    ///
+    /// Attach the source file this error came from, for reporting through [`miette`].
+    ///
+    pub fn into_diagnostic(self, src: &SourceFile) -> LexDiagnostic {
+        LexDiagnostic::new(self, src)
+    }
+}
+
+///
+/// Report `error` without its source, as [`LexError::into_diagnostic`]
+/// would with it. [`LexDiagnostic::span`] explains what this costs.
+///
+impl From<LexError> for LexDiagnostic {
+    fn from(error: LexError) -> Self {
+        let span = error.span();
+        Self {
+            source: None,
+            span: (span.start()..span.end()).into(),
+            error,
+        }
+    }
+}
+
+impl fmt::Display for LexDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error.message())
+    }
+}
+
+impl Error for LexDiagnostic {}
+
+impl Diagnostic for LexDiagnostic {
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &dyn miette::SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let label = LabeledSpan::new_with_span(Some(self.error.message()), self.span);
+        Some(Box::new(iter::once(label)))
+    }
+}
@@ -0,0 +1,57 @@
+//!
+//! Single digits, used to build up numbers and escapes.
+//!
+
+use crate::common::{SourceIter, Span, Spanned};
+
+use super::{Lex, LexError};
+
+macro_rules! digit {
+    ($(#[$attr:meta])* $name:ident, $radix:literal) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Spanned)]
+        pub struct $name {
+            span: Span,
+            value: u8,
+        }
+
+        impl $name {
+            ///
+            /// The numeric value of this digit.
+            ///
+            pub fn value(&self) -> u8 {
+                self.value
+            }
+        }
+
+        impl Lex for $name {
+            fn peek(input: &SourceIter) -> bool {
+                input.peek().is_some_and(|ch| ch.is_digit($radix))
+            }
+
+            fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+                match input.peek().and_then(|ch| ch.to_digit($radix)) {
+                    Some(value) => {
+                        let at = input.offset();
+                        input.next();
+                        Ok(Self {
+                            span: Span::from(at..=at),
+                            value: value as u8,
+                        })
+                    }
+                    None => Err(LexError::unexpected(input)),
+                }
+            }
+        }
+    };
+}
+
+digit! {
+    /// One of `0-9`.
+    DecimalDigit, 10
+}
+
+digit! {
+    /// One of `0-9`, `a-f`, or `A-F`.
+    HexDigit, 16
+}
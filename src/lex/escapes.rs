@@ -0,0 +1,452 @@
+//!
+//! Escape sequences, as found in strings and identifiers.
+//!
+//! Each of these starts *after* the backslash,
+//! except for [`LineContinuation`].
+//!
+//! See [EscapeSequence](https://262.ecma-international.org/5.1/#sec-7.8.4)
+//! in the ECMAScript spec.
+//!
+
+use alloc::{format, string::String};
+use core::fmt::Write;
+
+use crate::common::{SourceIter, Span, Spanned};
+
+use super::{
+    combinators::Exactly,
+    digits::HexDigit,
+    line_terminator::{is_line_terminator, LineTerminatorSeq},
+    strings::QuoteStyle,
+    Lex, LexError,
+};
+
+///
+/// Any escape sequence.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub enum EscapeSequence {
+    Single(SingleEscapeChar),
+    NonEscape(NonEscapeChar),
+    Null(Null),
+    Hex(HexEscapeSequence),
+    Unicode(UnicodeEscapeSequence),
+}
+
+impl EscapeSequence {
+    ///
+    /// The character this escape stands for.
+    ///
+    /// A unicode escape in the surrogate range (U+D800 to U+DFFF) is only
+    /// half of a character, so this returns `None`: the caller must combine
+    /// it with the other half of the pair.
+    ///
+    pub fn decode(&self) -> Option<char> {
+        match self {
+            Self::Single(escape) => Some(escape.resolve()),
+            Self::NonEscape(escape) => Some(escape.resolve()),
+            Self::Null(_) => Some('\0'),
+            Self::Hex(escape) => Some(escape.as_char()),
+            Self::Unicode(escape) => char::from_u32(u32::from(escape.code_unit())),
+        }
+    }
+}
+
+impl Lex for EscapeSequence {
+    fn peek(input: &SourceIter) -> bool {
+        SingleEscapeChar::peek(input)
+            || NonEscapeChar::peek(input)
+            || Null::peek(input)
+            || HexEscapeSequence::peek(input)
+            || UnicodeEscapeSequence::peek(input)
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        if SingleEscapeChar::peek(input) {
+            return SingleEscapeChar::lex(input).map(Self::Single);
+        }
+
+        if Null::peek(input) {
+            return Null::lex(input).map(Self::Null);
+        }
+
+        if HexEscapeSequence::peek(input) {
+            return HexEscapeSequence::lex(input).map(Self::Hex);
+        }
+
+        if UnicodeEscapeSequence::peek(input) {
+            return UnicodeEscapeSequence::lex(input).map(Self::Unicode);
+        }
+
+        if NonEscapeChar::peek(input) {
+            return NonEscapeChar::lex(input).map(Self::NonEscape);
+        }
+
+        if input.is_eof() {
+            return Err(LexError::unexpected(input));
+        }
+
+        let at = input.offset();
+        Err(LexError::InvalidEscape {
+            span: Span::new(at, at + 1),
+        })
+    }
+}
+
+///
+/// Combine a UTF-16 surrogate pair, written as two unicode escapes
+/// such as `\uD83D\uDCA9`, into the character it encodes.
+///
+/// Returns `None` unless `high` is a high surrogate (U+D800 to U+DBFF)
+/// and `low` is a low surrogate (U+DC00 to U+DFFF).
+///
+/// ```
+/// use avjason::lex::escapes::combine_surrogates;
+///
+/// assert_eq!(combine_surrogates(0xD83D, 0xDCA9), Some('💩'));
+/// assert_eq!(combine_surrogates(0xDCA9, 0xD83D), None);
+/// ```
+///
+pub fn combine_surrogates(high: u16, low: u16) -> Option<char> {
+    if !(0xD800..0xDC00).contains(&high) || !(0xDC00..0xE000).contains(&low) {
+        return None;
+    }
+
+    let high = u32::from(high - 0xD800);
+    let low = u32::from(low - 0xDC00);
+    char::from_u32(0x10000 + (high << 10) + low)
+}
+
+///
+/// Escape `text` to be written between `quote`s in a string literal,
+/// so that decoding the literal gives back `text`.
+///
+/// Backslashes, `quote` itself, line terminators and other control
+/// characters are escaped, using a single escape character where
+/// there is one, as in `\n`, or else a hex or unicode escape.
+/// Everything else is written as it is.
+///
+/// ```
+/// use avjason::lex::{escapes::escape, strings::QuoteStyle};
+///
+/// assert_eq!(escape("it's \"a\"\n", QuoteStyle::Single), r#"it\'s "a"\n"#);
+/// assert_eq!(escape("it's \"a\"\n", QuoteStyle::Double), r#"it's \"a\"\n"#);
+/// assert_eq!(escape("\0\u{2028}é", QuoteStyle::Double), r"\x00\u2028é");
+/// ```
+///
+pub fn escape(text: &str, quote: QuoteStyle) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        let single = match ch {
+            '\u{8}' => 'b',
+            '\t' => 't',
+            '\n' => 'n',
+            '\u{B}' => 'v',
+            '\u{C}' => 'f',
+            '\r' => 'r',
+            '\\' => '\\',
+            _ if ch == quote.as_char() => ch,
+            // `\0` would run into a following digit, so this is `\x00`.
+            _ if ch.is_control() && u32::from(ch) < 0x100 => {
+                write!(escaped, "\\x{:02X}", u32::from(ch)).expect("writing to a string");
+                continue;
+            }
+            _ if ch.is_control() || is_line_terminator(ch) => {
+                write!(escaped, "\\u{:04X}", u32::from(ch)).expect("writing to a string");
+                continue;
+            }
+            _ => {
+                escaped.push(ch);
+                continue;
+            }
+        };
+
+        escaped.push('\\');
+        escaped.push(single);
+    }
+
+    escaped
+}
+
+///
+/// Consume a single character, if `predicate` accepts it.
+///
+fn lex_char(
+    input: &mut SourceIter,
+    predicate: impl Fn(char) -> bool,
+) -> Result<(Span, char), LexError> {
+    match input.peek() {
+        Some(ch) if predicate(ch) => {
+            let at = input.offset();
+            input.next();
+            Ok((Span::from(at..=at), ch))
+        }
+        _ => Err(LexError::unexpected(input)),
+    }
+}
+
+fn is_single_escape_char(ch: char) -> bool {
+    matches!(ch, '\'' | '"' | '\\' | 'b' | 'f' | 'n' | 'r' | 't' | 'v')
+}
+
+///
+/// One of `'`, `"`, `\`, `b`, `f`, `n`, `r`, `t`, or `v`.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct SingleEscapeChar {
+    span: Span,
+    ch: char,
+}
+
+impl SingleEscapeChar {
+    ///
+    /// The character after the backslash, such as `n` in `\n`.
+    ///
+    pub fn raw_char(&self) -> char {
+        self.ch
+    }
+
+    ///
+    /// The character this escape stands for, following the
+    /// [SingleEscapeCharacter](https://262.ecma-international.org/5.1/#sec-7.8.4)
+    /// table in the ECMAScript spec.
+    ///
+    /// | Escape | Character              |
+    /// |--------|------------------------|
+    /// | `\b`   | U+0008 backspace       |
+    /// | `\t`   | U+0009 tab             |
+    /// | `\n`   | U+000A line feed       |
+    /// | `\v`   | U+000B vertical tab    |
+    /// | `\f`   | U+000C form feed       |
+    /// | `\r`   | U+000D carriage return |
+    /// | `\"`   | U+0022 `"`             |
+    /// | `\'`   | U+0027 `'`             |
+    /// | `\\`   | U+005C `\`             |
+    ///
+    pub fn resolve(&self) -> char {
+        match self.ch {
+            'b' => '\u{8}',
+            't' => '\t',
+            'n' => '\n',
+            'v' => '\u{B}',
+            'f' => '\u{C}',
+            'r' => '\r',
+            quote_or_backslash => quote_or_backslash,
+        }
+    }
+}
+
+impl Lex for SingleEscapeChar {
+    fn peek(input: &SourceIter) -> bool {
+        input.peek().is_some_and(is_single_escape_char)
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let (span, ch) = lex_char(input, is_single_escape_char)?;
+        Ok(Self { span, ch })
+    }
+}
+
+///
+/// Any character which does not have a special meaning
+/// when escaped, and so stands for itself: `\q` is `q`.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct NonEscapeChar {
+    span: Span,
+    ch: char,
+}
+
+impl NonEscapeChar {
+    fn is_non_escape_char(ch: char) -> bool {
+        !(is_single_escape_char(ch)
+            || ch.is_ascii_digit()
+            || ch == 'x'
+            || ch == 'u'
+            || is_line_terminator(ch))
+    }
+
+    ///
+    /// The escaped character.
+    ///
+    pub fn raw_char(&self) -> char {
+        self.ch
+    }
+
+    ///
+    /// The character this escape stands for,
+    /// which is just the [escaped character](NonEscapeChar::raw_char).
+    ///
+    pub fn resolve(&self) -> char {
+        self.ch
+    }
+}
+
+impl Lex for NonEscapeChar {
+    fn peek(input: &SourceIter) -> bool {
+        input.peek().is_some_and(Self::is_non_escape_char)
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let (span, ch) = lex_char(input, Self::is_non_escape_char)?;
+        Ok(Self { span, ch })
+    }
+}
+
+///
+/// `\0`, not followed by another digit.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct Null {
+    span: Span,
+}
+
+impl Lex for Null {
+    fn peek(input: &SourceIter) -> bool {
+        input.peek() == Some('0') && !input.peek2().is_some_and(|ch| ch.is_ascii_digit())
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        if !Self::peek(input) {
+            return Err(LexError::unexpected(input));
+        }
+
+        let (span, _) = lex_char(input, |ch| ch == '0')?;
+        Ok(Self { span })
+    }
+}
+
+///
+/// Lex `prefix` followed by exactly `N` hex digits.
+///
+fn lex_hex_digits<const N: usize>(
+    input: &mut SourceIter,
+    prefix: char,
+) -> Result<(Span, Exactly<N, HexDigit>), LexError> {
+    let (start, _) = lex_char(input, |ch| ch == prefix)?;
+
+    let digits = Exactly::lex(input).map_err(|_| {
+        LexError::expected(
+            start.start()..input.offset(),
+            format!("{N} hexadecimal digits after `\\{prefix}`"),
+        )
+    })?;
+
+    Ok((Span::new(start.start(), input.offset()), digits))
+}
+
+///
+/// `x` followed by two hex digits, as in `\x41`.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct HexEscapeSequence {
+    span: Span,
+    digits: Exactly<2, HexDigit>,
+}
+
+impl HexEscapeSequence {
+    ///
+    /// The hex digits, as written: `4` and `1` in `\x41`.
+    ///
+    pub fn digits(&self) -> &[HexDigit; 2] {
+        self.digits.as_array()
+    }
+
+    ///
+    /// The character this escape stands for.
+    ///
+    /// Two hex digits can only encode U+0000 to U+00FF, all of which
+    /// are valid characters (Latin-1), so this cannot fail.
+    ///
+    pub fn as_char(&self) -> char {
+        char::from(self.digits.iter().fold(0, |acc, d| acc * 16 + d.value()))
+    }
+}
+
+impl Lex for HexEscapeSequence {
+    fn peek(input: &SourceIter) -> bool {
+        input.peek() == Some('x')
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let (span, digits) = lex_hex_digits(input, 'x')?;
+        Ok(Self { span, digits })
+    }
+}
+
+///
+/// `u` followed by four hex digits, as in `\u0041`.
+///
+/// This is a UTF-16 code unit, so characters outside
+/// the Basic Multilingual Plane need two of these.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct UnicodeEscapeSequence {
+    span: Span,
+    digits: Exactly<4, HexDigit>,
+}
+
+impl UnicodeEscapeSequence {
+    ///
+    /// The hex digits, as written: `0`, `0`, `4` and `1` in `\u0041`.
+    ///
+    pub fn digits(&self) -> &[HexDigit; 4] {
+        self.digits.as_array()
+    }
+
+    ///
+    /// The UTF-16 code unit this escape stands for.
+    ///
+    /// This may be half of a surrogate pair,
+    /// see [`combine_surrogates`].
+    ///
+    pub fn code_unit(&self) -> u16 {
+        self.digits
+            .iter()
+            .fold(0, |acc, d| acc * 16 + u16::from(d.value()))
+    }
+}
+
+impl Lex for UnicodeEscapeSequence {
+    fn peek(input: &SourceIter) -> bool {
+        input.peek() == Some('u')
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let (span, digits) = lex_hex_digits(input, 'u')?;
+        Ok(Self { span, digits })
+    }
+}
+
+///
+/// A backslash followed by a line terminator, which lets
+/// a string literal carry on over the next line.
+///
+/// Unlike the other escapes, this includes its backslash.
+/// It does not stand for any character, so a string's value
+/// does not include it at all.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct LineContinuation {
+    span: Span,
+}
+
+impl Lex for LineContinuation {
+    fn peek(input: &SourceIter) -> bool {
+        input.peek() == Some('\\') && input.peek2().is_some_and(is_line_terminator)
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        if !Self::peek(input) {
+            return Err(LexError::unexpected(input));
+        }
+
+        let start = input.offset();
+        input.next();
+        let terminator = LineTerminatorSeq::lex(input)?;
+
+        Ok(Self {
+            span: Span::new(start, terminator.span().end()),
+        })
+    }
+}
@@ -0,0 +1,315 @@
+//!
+//! Identifier names, used as unquoted object keys.
+//!
+
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    string::String,
+};
+
+use crate::common::{SourceFile, SourceIter, Span, Spanned};
+
+use super::{escapes::UnicodeEscapeSequence, incremental::Shift, Lex, LexError};
+
+///
+/// An [IdentifierName](https://262.ecma-international.org/5.1/#sec-7.6).
+///
+/// Identifiers may contain unicode escapes, such as `\u0061bc`,
+/// as long as each one stands for a character which would be allowed
+/// in its place unescaped.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct LIdentifier {
+    span: Span,
+    // The decoded name, only if it contains any escapes.
+    escaped: Option<Box<str>>,
+}
+
+///
+/// A word which JSON5 uses as a literal value.
+///
+/// `Infinity` and `NaN` are lexed as [numbers](super::number::Number) instead.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Keyword {
+    ///
+    /// `true`
+    ///
+    True,
+    ///
+    /// `false`
+    ///
+    False,
+    ///
+    /// `null`
+    ///
+    Null,
+}
+
+impl Keyword {
+    ///
+    /// How this keyword is spelt.
+    ///
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::True => "true",
+            Self::False => "false",
+            Self::Null => "null",
+        }
+    }
+}
+
+///
+/// Is the upcoming input a `\u` followed by four hex digits?
+///
+fn peek_unicode_escape(input: &SourceIter) -> bool {
+    let mut fork = input.fork();
+    matches!(fork.next(), Some((_, '\\')))
+        && matches!(fork.next(), Some((_, 'u')))
+        && (0..4).all(|_| fork.next().is_some_and(|(_, ch)| ch.is_ascii_hexdigit()))
+}
+
+///
+/// Can `ch` start an identifier?
+///
+fn is_start_char(ch: char) -> bool {
+    matches!(ch, '$' | '_') || unicode_ident::is_xid_start(ch)
+}
+
+///
+/// Can `ch` continue an identifier?
+///
+fn is_part_char(ch: char) -> bool {
+    // Zero width (non-)joiners.
+    matches!(ch, '\u{200C}' | '\u{200D}') || unicode_ident::is_xid_continue(ch) || is_start_char(ch)
+}
+
+impl LIdentifier {
+    ///
+    /// Can `name` be written as an identifier as it is,
+    /// without quotes or escapes?
+    ///
+    /// Reserved words like `null` count, since they are still
+    /// valid identifier names, and so valid object keys.
+    ///
+    /// ```
+    /// use avjason::lex::identifier::LIdentifier;
+    ///
+    /// assert!(LIdentifier::is_identifier_name("$camelCase_2"));
+    /// assert!(LIdentifier::is_identifier_name("null"));
+    /// assert!(!LIdentifier::is_identifier_name("two words"));
+    /// assert!(!LIdentifier::is_identifier_name("2d"));
+    /// assert!(!LIdentifier::is_identifier_name(""));
+    /// ```
+    ///
+    pub fn is_identifier_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars.next().is_some_and(is_start_char) && chars.all(is_part_char)
+    }
+
+    ///
+    /// Can the upcoming input start an identifier?
+    ///
+    pub fn is_identifier_start(input: &SourceIter) -> bool {
+        match input.peek() {
+            Some('\\') => peek_unicode_escape(input),
+            Some(ch) => is_start_char(ch),
+            None => false,
+        }
+    }
+
+    ///
+    /// Can the upcoming input continue an identifier?
+    ///
+    pub fn is_identifier_part(input: &SourceIter) -> bool {
+        match input.peek() {
+            Some('\\') => peek_unicode_escape(input),
+            Some(ch) => is_part_char(ch),
+            None => false,
+        }
+    }
+
+    ///
+    /// The keyword this identifier spells, if any.
+    ///
+    /// Keywords must be written out in full:
+    /// an escaped spelling, such as `tru\u0065`, is just an identifier.
+    ///
+    pub fn as_keyword(&self, src: &SourceFile) -> Option<Keyword> {
+        match self.raw(src) {
+            "true" => Some(Keyword::True),
+            "false" => Some(Keyword::False),
+            "null" => Some(Keyword::Null),
+            _ => None,
+        }
+    }
+
+    ///
+    /// The name of this identifier, with any unicode escapes decoded,
+    /// so that `\u0061bc` and `abc` have the same name.
+    ///
+    /// Identifiers without escapes borrow their name straight from `src`,
+    /// so this only allocates (once, whilst lexing) for escaped identifiers.
+    ///
+    pub fn as_str<'a>(&'a self, src: &'a SourceFile) -> &'a str {
+        match &self.escaped {
+            Some(name) => name,
+            None => self.raw(src),
+        }
+    }
+
+    ///
+    /// This identifier's [name](LIdentifier::as_str), borrowed from `src`
+    /// unless it had escapes to decode.
+    ///
+    pub fn name<'a>(&self, src: &'a SourceFile) -> Cow<'a, str> {
+        match &self.escaped {
+            Some(name) => Cow::Owned(name.clone().into_string()),
+            None => Cow::Borrowed(self.raw(src)),
+        }
+    }
+
+    ///
+    /// This identifier, paired with its [name](LIdentifier::as_str) from `src`,
+    /// so the name can be read without passing `src` around.
+    ///
+    /// This never copies the name: an escaped identifier's decoded name
+    /// is moved out of the token.
+    ///
+    pub fn with_source(self, src: &SourceFile) -> IdentifierName<'_> {
+        let name = match self.escaped {
+            Some(name) => Cow::Owned(name.into_string()),
+            None => Cow::Borrowed(src.slice(self.span)),
+        };
+
+        IdentifierName {
+            span: self.span,
+            name,
+        }
+    }
+
+    ///
+    /// Consume one character of an identifier, or a whole unicode escape,
+    /// checking that it is allowed by `is_valid`.
+    ///
+    /// Returns the character, and whether it was escaped.
+    ///
+    fn lex_char(
+        input: &mut SourceIter,
+        is_valid: fn(char) -> bool,
+    ) -> Result<(char, bool), LexError> {
+        let start = input.offset();
+        let (_, ch) = input.next().ok_or_else(|| LexError::unexpected(input))?;
+        if ch != '\\' {
+            return Ok((ch, false));
+        }
+
+        let escape = UnicodeEscapeSequence::lex(input)?;
+        match char::from_u32(escape.code_unit().into()) {
+            Some(ch) if is_valid(ch) => Ok((ch, true)),
+            _ => Err(LexError::InvalidIdentifierEscape {
+                span: Span::new(start, input.offset()),
+            }),
+        }
+    }
+}
+
+impl Shift for LIdentifier {
+    fn shift(&mut self, by: isize) {
+        self.span = self.span.shift(by);
+    }
+}
+
+impl Lex for LIdentifier {
+    fn peek(input: &SourceIter) -> bool {
+        Self::is_identifier_start(input)
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        if !Self::peek(input) {
+            return Err(LexError::unexpected(input));
+        }
+
+        let start = input.offset();
+        let mut escaped: Option<String> = None;
+        let mut is_valid: fn(char) -> bool = is_start_char;
+        loop {
+            let at = input.offset();
+            let (ch, is_escape) = Self::lex_char(input, is_valid)?;
+
+            // Only build the name separately once it differs from the source.
+            if is_escape && escaped.is_none() {
+                escaped = Some(input.file().slice(Span::new(start, at)).to_owned());
+            }
+
+            if let Some(name) = &mut escaped {
+                name.push(ch);
+            }
+
+            if !Self::is_identifier_part(input) {
+                break;
+            }
+
+            is_valid = is_part_char;
+        }
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+            escaped: escaped.map(String::into_boxed_str),
+        })
+    }
+}
+
+///
+/// An identifier along with its name, decoded and ready to use
+/// without the [`SourceFile`] it was lexed from, as when building
+/// a table of names.
+///
+/// Identifiers without escapes borrow their name straight from the source,
+/// so lexing one never allocates.
+///
+/// ```
+/// use avjason::{common::SourceFile, lex::identifier::IdentifierName};
+///
+/// let src = SourceFile::dummy_file(r"\u0061bc");
+/// let ident = IdentifierName::lex(&mut src.iter()).unwrap();
+/// assert_eq!(ident.as_str(), "abc");
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct IdentifierName<'a> {
+    span: Span,
+    name: Cow<'a, str>,
+}
+
+impl<'a> IdentifierName<'a> {
+    ///
+    /// Does the upcoming input look like an identifier?
+    ///
+    pub fn peek(input: &SourceIter) -> bool {
+        LIdentifier::peek(input)
+    }
+
+    ///
+    /// Lex an identifier, keeping its name.
+    ///
+    pub fn lex(input: &mut SourceIter<'a>) -> Result<Self, LexError> {
+        let file = input.file();
+        LIdentifier::lex(input).map(|ident| ident.with_source(file))
+    }
+
+    ///
+    /// The name, with any unicode escapes decoded.
+    ///
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+
+    ///
+    /// Take the name out, borrowed from the source unless it had escapes.
+    ///
+    pub fn into_name(self) -> Cow<'a, str> {
+        self.name
+    }
+}
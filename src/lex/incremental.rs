@@ -0,0 +1,154 @@
+//!
+//! Re-lexing just the part of a file an edit changed,
+//! for editors which keep the elements of a large file up to date.
+//!
+
+use alloc::{vec, vec::Vec};
+use core::{iter, ops::Range};
+
+use crate::common::{SourceFile, Spanned};
+
+use super::{InputElement, LexError, TokensWithTrivia};
+
+///
+/// Moves the spans of an already-lexed element.
+///
+pub(crate) trait Shift {
+    ///
+    /// Move every span in `self` by `by` characters.
+    ///
+    fn shift(&mut self, by: isize);
+}
+
+///
+/// A change to the text of a source file: the characters in `range`
+/// of the old text were replaced by `inserted` new characters.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub inserted: usize,
+}
+
+impl Edit {
+    ///
+    /// How many characters later everything after the edit is
+    /// in the new text (or earlier, if negative).
+    ///
+    pub fn delta(&self) -> isize {
+        self.inserted as isize - self.range.len() as isize
+    }
+}
+
+///
+/// The result of [`relex`].
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relexed {
+    ///
+    /// Which of the new elements were lexed again:
+    /// everything else was kept from before the edit.
+    ///
+    pub elements: Range<usize>,
+    ///
+    /// Errors from the part of the file which was lexed again.
+    ///
+    pub errors: Vec<LexError>,
+}
+
+///
+/// Bring `elements`, lexed (as by [`lex_all_recovering`](super::lex_all_recovering))
+/// from a file before `edit`, up to date with `src`, the file after it.
+///
+/// Lexing starts again just before the edit (or from the first error
+/// before it, if there was one), and stops as soon as it
+/// reaches the start of an old element after the edit: from there on,
+/// the text is the same as before, so it would lex the same way.
+/// Those elements are kept, with their spans moved by [`Edit::delta`].
+///
+/// ```
+/// use avjason::{
+///     common::SourceFile,
+///     lex::{incremental::{relex, Edit}, lex_all_recovering},
+/// };
+///
+/// let before = SourceFile::dummy_file("[1, 2, 3]");
+/// let (mut elements, _) = lex_all_recovering(&before);
+///
+/// // Replace `2` with `'two'`.
+/// let after = SourceFile::dummy_file("[1, 'two', 3]");
+/// let relexed = relex(&after, &mut elements, &Edit { range: 4..5, inserted: 5 });
+///
+/// assert_eq!(elements, lex_all_recovering(&after).0);
+/// assert_eq!(relexed.elements, 2..5);
+/// ```
+///
+pub fn relex(src: &SourceFile, elements: &mut Vec<InputElement>, edit: &Edit) -> Relexed {
+    let delta = edit.delta();
+
+    // The element before the first one touching the edit might have
+    // stopped where it did because of what came after it, so start there.
+    let touched = elements.partition_point(|element| element.span().end() < edit.range.start);
+    let mut first = touched.saturating_sub(1);
+
+    // A gap between elements is where lexing failed and skipped ahead,
+    // but the attempt which failed might have looked as far as the edit,
+    // so start from the first gap instead.
+    let file_start = src.iter().offset();
+    let ends = iter::once(file_start).chain(elements.iter().map(|element| element.span().end()));
+    if let Some(gap) = elements
+        .iter()
+        .take(first + 1)
+        .zip(ends)
+        .position(|(element, end_before)| element.span().start() != end_before)
+    {
+        first = gap;
+    }
+
+    let input = match first {
+        0 => src.iter(),
+        _ => src.iter_at(elements[first - 1].span().end()),
+    };
+
+    // Old elements which started after the edit, and might be kept.
+    let mut kept = elements.partition_point(|element| element.span().start() < edit.range.end);
+    let shifted = |element: &InputElement| element.span().start().saturating_add_signed(delta);
+    let edit_end = edit.range.start + edit.inserted;
+
+    let mut lexer = TokensWithTrivia::new(input);
+    let mut fresh = vec![];
+    let mut errors = vec![];
+    loop {
+        let at = lexer.offset();
+        if at >= edit_end {
+            while kept < elements.len() && shifted(&elements[kept]) < at {
+                kept += 1;
+            }
+
+            if kept < elements.len() && shifted(&elements[kept]) == at {
+                break;
+            }
+        }
+
+        match lexer.next() {
+            Some(Ok(element)) => fresh.push(element),
+            Some(Err(error)) => errors.push(error),
+            None => {
+                kept = elements.len();
+                break;
+            }
+        }
+    }
+
+    for element in &mut elements[kept..] {
+        element.shift(delta);
+    }
+
+    let relexed = first..first + fresh.len();
+    elements.splice(first..kept, fresh);
+
+    Relexed {
+        elements: relexed,
+        errors,
+    }
+}
@@ -0,0 +1,54 @@
+//!
+//! Line terminators.
+//!
+
+use crate::common::{SourceIter, Span, Spanned};
+
+use super::{incremental::Shift, Lex, LexError};
+
+///
+/// Is `ch` a [LineTerminator](https://262.ecma-international.org/5.1/#sec-7.3)?
+///
+pub fn is_line_terminator(ch: char) -> bool {
+    matches!(ch, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+///
+/// A single line break.
+///
+/// A carriage return followed by a line feed (`\r\n`)
+/// is one sequence, not two.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct LineTerminatorSeq {
+    span: Span,
+}
+
+impl Shift for LineTerminatorSeq {
+    fn shift(&mut self, by: isize) {
+        self.span = self.span.shift(by);
+    }
+}
+
+impl Lex for LineTerminatorSeq {
+    fn peek(input: &SourceIter) -> bool {
+        input.peek().is_some_and(is_line_terminator)
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        if !Self::peek(input) {
+            return Err(LexError::unexpected(input));
+        }
+
+        let start = input.offset();
+        if let Some((_, '\r')) = input.next() {
+            if input.peek() == Some('\n') {
+                input.next();
+            }
+        }
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+        })
+    }
+}
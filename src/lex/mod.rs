@@ -0,0 +1,502 @@
+//!
+//! Lexical grammar of JSON5.
+//!
+//! See the [JSON5 specification](https://spec.json5.org/#lexical-grammar).
+//!
+//! Every token, down to single escape sequences and digits, implements [`Lex`]
+//! over the same cursor, [`SourceIter`], so any token can be lexed from any
+//! position and the results combined freely. Lexers look ahead with
+//! [`SourceIter::peek`] and friends, or a [fork](SourceIter::fork), and
+//! only consume input once they are committed to a token.
+//!
+
+pub mod combinators;
+pub mod comment;
+#[cfg(feature = "miette")]
+mod diagnostic;
+pub mod digits;
+pub mod escapes;
+pub mod identifier;
+pub mod incremental;
+pub mod line_terminator;
+pub mod number;
+pub mod strings;
+pub mod tokens;
+pub mod trivia;
+pub mod whitespace;
+
+use alloc::{format, string::String, vec, vec::Vec};
+use core::{error::Error, fmt, iter::FusedIterator};
+
+use crate::common::{SourceFile, SourceIter, Span, Spanned};
+
+#[cfg(feature = "miette")]
+pub use self::diagnostic::LexDiagnostic;
+
+use self::{
+    comment::Comment,
+    incremental::Shift,
+    line_terminator::{is_line_terminator, LineTerminatorSeq},
+    tokens::Token,
+    whitespace::{is_whitespace, WhiteSpace},
+};
+
+///
+/// A token which can be lexed from a source file.
+///
+pub trait Lex: Sized {
+    ///
+    /// Does the upcoming input look like the start of this token?
+    ///
+    /// This must not consume any input.
+    ///
+    fn peek(input: &SourceIter) -> bool;
+
+    ///
+    /// Consume this token from the input.
+    ///
+    /// This should only be called after [`Lex::peek`] returns `true`,
+    /// but must return an error (rather than panic) otherwise.
+    ///
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError>;
+}
+
+///
+/// Derives [`Lex`](trait@Lex) for a fixed token, or an enum of tokens.
+///
+/// A struct holding only its span is a token written exactly as
+/// the character or string in its `#[lex(...)]` attribute:
+///
+/// ```
+/// use avjason::{common::{SourceFile, Span, Spanned}, lex::Lex};
+///
+/// #[derive(Spanned, Lex)]
+/// #[lex("=>")]
+/// struct Arrow(Span);
+///
+/// let src = SourceFile::dummy_file("=>");
+/// assert_eq!(Arrow::lex(&mut src.iter()).unwrap().span(), Span::new(0, 2));
+/// ```
+///
+/// Add `ignore_case` to match the literal regardless of case,
+/// as in `#[lex("nan", ignore_case)]`.
+/// The span still covers the text exactly as it was written.
+///
+/// Add `keyword` for a literal which is a word, so it does not match
+/// the start of a longer identifier: `#[lex("null", keyword)]` matches
+/// `null` and `null,`, but not `nullable`.
+///
+/// For an enum, each variant must hold exactly one token.
+/// The enum peeks if any of its variants do,
+/// and is lexed as the first variant to peek, in the order they are declared.
+///
+/// ```
+/// use avjason::{
+///     common::SourceFile,
+///     lex::{tokens::{Colon, Comma}, Lex},
+/// };
+///
+/// #[derive(Lex)]
+/// enum Separator {
+///     Colon(Colon),
+///     Comma(Comma),
+/// }
+///
+/// let src = SourceFile::dummy_file(",");
+/// assert!(Separator::peek(&src.iter()));
+/// assert!(matches!(Separator::lex(&mut src.iter()), Ok(Separator::Comma(_))));
+/// ```
+///
+/// Where one variant could start with another, the longer should be tried first.
+/// Rather than reordering the variants, give it a higher `#[lex(priority = N)]`:
+/// variants are tried from the highest priority down, 0 by default,
+/// and in the order they are declared otherwise.
+///
+/// ```
+/// # use avjason::{common::{SourceFile, Span}, lex::{tokens::Dot, Lex}};
+/// #[derive(Lex)]
+/// #[lex("...")]
+/// struct Ellipsis(Span);
+///
+/// #[derive(Lex)]
+/// enum Dots {
+///     Dot(Dot),
+///     #[lex(priority = 1)]
+///     Ellipsis(Ellipsis),
+/// }
+///
+/// let src = SourceFile::dummy_file("...");
+/// assert!(matches!(Dots::lex(&mut src.iter()), Ok(Dots::Ellipsis(_))));
+/// ```
+///
+/// Anything else is rejected with an error explaining what is expected.
+/// A struct must have a literal to be written as:
+///
+/// ```compile_fail
+/// # use avjason::{common::Span, lex::Lex};
+/// #[derive(Lex)]
+/// struct Arrow(Span);
+/// ```
+///
+/// and must hold nothing but its span:
+///
+/// ```compile_fail
+/// # use avjason::{common::Span, lex::Lex};
+/// #[derive(Lex)]
+/// #[lex("=>")]
+/// struct Arrow {
+///     span: Span,
+///     text: String,
+/// }
+/// ```
+///
+/// The literal must be a character or a non-empty string:
+///
+/// ```compile_fail
+/// # use avjason::{common::Span, lex::Lex};
+/// #[derive(Lex)]
+/// #[lex(1)]
+/// struct One(Span);
+/// ```
+///
+/// An enum cannot have a literal:
+///
+/// ```compile_fail
+/// # use avjason::lex::{tokens::Colon, Lex};
+/// #[derive(Lex)]
+/// #[lex(':')]
+/// enum Separator {
+///     Colon(Colon),
+/// }
+/// ```
+///
+/// and each of its variants must hold exactly one token:
+///
+/// ```compile_fail
+/// # use avjason::lex::{tokens::Colon, Lex};
+/// #[derive(Lex)]
+/// enum Separator {
+///     Colon(Colon),
+///     Nothing,
+/// }
+/// ```
+///
+pub use avjason_macros::Lex;
+
+///
+/// Optional extensions to the JSON5 lexical grammar.
+///
+/// Everything is off by default, which follows the spec exactly.
+/// Use [`SourceFile::iter_with`](crate::common::SourceFile::iter_with)
+/// to lex with other options.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LexOptions {
+    ///
+    /// Allow `/* block comments /* to nest */ */`.
+    ///
+    pub nested_comments: bool,
+}
+
+///
+/// Something went wrong whilst lexing.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub enum LexError {
+    ///
+    /// A character which cannot start (or continue) the token being lexed.
+    ///
+    UnexpectedChar { span: Span, found: char },
+
+    ///
+    /// The input ended where a token was expected.
+    ///
+    UnexpectedEof { span: Span },
+
+    ///
+    /// Something specific was missing, like the digits after `0x`.
+    ///
+    Expected { span: Span, what: String },
+
+    ///
+    /// A backslash followed by something other than an escape sequence.
+    ///
+    InvalidEscape { span: Span },
+
+    ///
+    /// A unicode escape for a character which cannot appear
+    /// at that point in an identifier.
+    ///
+    InvalidIdentifierEscape { span: Span },
+
+    ///
+    /// A `\uD800`-style escape for half of a surrogate pair,
+    /// without the other half straight after it.
+    ///
+    UnpairedSurrogate { span: Span },
+
+    ///
+    /// A string literal which is not closed before the end of its line,
+    /// or the end of the input.
+    ///
+    UnterminatedString { span: Span },
+
+    ///
+    /// A control character written directly in a string literal.
+    ///
+    UnescapedControlChar { span: Span, found: char },
+
+    ///
+    /// A block comment which is not closed before the end of the input.
+    ///
+    UnterminatedComment { span: Span },
+
+    ///
+    /// A decimal literal with a leading zero, like `0123`.
+    ///
+    LegacyOctal { span: Span },
+
+    ///
+    /// A finite literal too large for an `f64`, like `1e400`.
+    ///
+    NumberTooLarge { span: Span },
+}
+
+impl LexError {
+    ///
+    /// An error for whatever character is next in the input.
+    ///
+    pub fn unexpected(input: &SourceIter) -> Self {
+        let at = input.offset();
+        match input.peek() {
+            Some(found) => Self::UnexpectedChar {
+                span: Span::new(at, at + 1),
+                found,
+            },
+            None => Self::UnexpectedEof {
+                span: Span::empty(at),
+            },
+        }
+    }
+
+    ///
+    /// An error for a missing `what`, described as in "expected `what`".
+    ///
+    pub fn expected(span: impl Into<Span>, what: impl Into<String>) -> Self {
+        Self::Expected {
+            span: span.into(),
+            what: what.into(),
+        }
+    }
+
+    ///
+    /// A description of the problem.
+    ///
+    pub fn message(&self) -> String {
+        match self {
+            Self::UnexpectedChar { found, .. } => format!("unexpected character {found:?}"),
+            Self::UnexpectedEof { .. } => "unexpected end of input".into(),
+            Self::Expected { what, .. } => format!("expected {what}"),
+            Self::InvalidEscape { .. } => "invalid escape sequence".into(),
+            Self::InvalidIdentifierEscape { .. } => {
+                "unicode escape is not a valid identifier character here".into()
+            }
+            Self::UnpairedSurrogate { .. } => "unpaired surrogate in unicode escape".into(),
+            Self::UnterminatedString { .. } => "unterminated string literal".into(),
+            Self::UnescapedControlChar { found, .. } => format!(
+                "control character U+{:04X} must be escaped in a string literal",
+                *found as u32
+            ),
+            Self::UnterminatedComment { .. } => "unterminated block comment".into(),
+            Self::LegacyOctal { .. } => "legacy octal literals are not allowed: \
+                remove the leading zero, or use a hexadecimal literal (`0x...`)"
+                .into(),
+            Self::NumberTooLarge { .. } => "number is too large to be represented".into(),
+        }
+    }
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.span();
+        write!(f, "{} at {}..{}", self.message(), span.start(), span.end())
+    }
+}
+
+impl Error for LexError {}
+
+///
+/// Consume `expected` from the input, returning its offset.
+///
+pub(crate) fn expect(input: &mut SourceIter, expected: char) -> Result<usize, LexError> {
+    match input.peek() {
+        Some(ch) if ch == expected => {
+            let at = input.offset();
+            input.next();
+            Ok(at)
+        }
+        _ => Err(LexError::unexpected(input)),
+    }
+}
+
+///
+/// Lex every [`InputElement`] in `src`, carrying on past errors
+/// to report as many of them as possible at once.
+///
+/// After an error, lexing starts again from the next whitespace
+/// or line terminator after the start of the element which failed.
+/// Everything skipped over is left out of the elements returned.
+///
+/// ```
+/// use avjason::{common::SourceFile, lex::lex_all_recovering};
+///
+/// let src = SourceFile::dummy_file("[1, 0123, 3]");
+/// let (elements, errors) = lex_all_recovering(&src);
+///
+/// assert_eq!(elements.len(), 7);
+/// assert_eq!(errors.len(), 1);
+/// ```
+///
+pub fn lex_all_recovering(src: &SourceFile) -> (Vec<InputElement>, Vec<LexError>) {
+    // Real documents have an element every two to three bytes, so this
+    // is room for all of them, and the buffer never has to grow.
+    // Denser input, like `[1,2,3]`, only grows it once.
+    let mut elements = Vec::with_capacity(src.text().len() / 2);
+    let mut errors = vec![];
+
+    for element in src.tokens_with_trivia() {
+        match element {
+            Ok(element) => elements.push(element),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    (elements, errors)
+}
+
+///
+/// Every [`InputElement`] of a source file, or the errors lexing them,
+/// made by [`SourceFile::tokens_with_trivia`].
+///
+/// Lexing carries on after an error in the same way as [`lex_all_recovering`].
+///
+#[derive(Debug, Clone)]
+pub struct TokensWithTrivia<'a> {
+    input: SourceIter<'a>,
+}
+
+impl<'a> TokensWithTrivia<'a> {
+    pub(crate) fn new(input: SourceIter<'a>) -> Self {
+        Self { input }
+    }
+
+    ///
+    /// Character offset of the next element.
+    ///
+    pub(crate) fn offset(&self) -> usize {
+        self.input.offset()
+    }
+}
+
+impl Iterator for TokensWithTrivia<'_> {
+    type Item = Result<InputElement, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.input.is_eof() {
+            return None;
+        }
+
+        let start = self.input.checkpoint();
+        let result = InputElement::lex(&mut self.input);
+        if result.is_err() {
+            self.input.restore(start);
+
+            // Always skip at least one character, so lexing cannot get stuck.
+            self.input.next();
+            while self
+                .input
+                .peek()
+                .is_some_and(|ch| !is_whitespace(ch) && !is_line_terminator(ch))
+            {
+                self.input.next();
+            }
+        }
+
+        Some(result)
+    }
+}
+
+impl FusedIterator for TokensWithTrivia<'_> {}
+
+///
+/// Every [`Token`] of a source file, skipping whitespace, line terminators
+/// and comments, or the errors lexing them.
+/// Made by [`SourceFile::tokens`].
+///
+/// ```
+/// use avjason::common::{SourceFile, Spanned};
+///
+/// let src = SourceFile::dummy_file("[1, /* two */ 2]");
+/// let raw: Vec<_> = src.tokens().map(|token| token.unwrap().raw(&src).to_owned()).collect();
+/// assert_eq!(raw, ["[", "1", ",", "2", "]"]);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct Tokens<'a> {
+    elements: TokensWithTrivia<'a>,
+}
+
+impl<'a> Tokens<'a> {
+    pub(crate) fn new(input: SourceIter<'a>) -> Self {
+        Self {
+            elements: TokensWithTrivia::new(input),
+        }
+    }
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = Result<Token, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.find_map(|element| match element {
+            Ok(InputElement::Token(token)) => Some(Ok(token)),
+            Ok(_) => None,
+            Err(error) => Some(Err(error)),
+        })
+    }
+}
+
+impl FusedIterator for Tokens<'_> {}
+
+///
+/// Any element of a JSON5 source file.
+///
+/// See [InputElement](https://262.ecma-international.org/5.1/#sec-7) in the ECMAScript spec.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned, Lex)]
+pub enum InputElement {
+    WhiteSpace(WhiteSpace),
+    LineTerminator(LineTerminatorSeq),
+    Comment(Comment),
+    Token(Token),
+}
+
+impl Shift for InputElement {
+    fn shift(&mut self, by: isize) {
+        match self {
+            Self::WhiteSpace(element) => element.shift(by),
+            Self::LineTerminator(element) => element.shift(by),
+            Self::Comment(element) => element.shift(by),
+            Self::Token(element) => element.shift(by),
+        }
+    }
+}
+
+impl InputElement {
+    ///
+    /// Is this element whitespace, a line terminator, or a comment?
+    ///
+    pub fn is_trivia(&self) -> bool {
+        !matches!(self, Self::Token(_))
+    }
+}
@@ -0,0 +1,486 @@
+//!
+//! Numeric literals.
+//!
+
+use crate::common::{SourceFile, SourceIter, Span, Spanned};
+
+use super::{
+    digits::{DecimalDigit, HexDigit},
+    identifier::LIdentifier,
+    incremental::Shift,
+    Lex, LexError,
+};
+
+///
+/// The form a [`Number`] was written in.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberKind {
+    ///
+    /// A decimal literal, such as `42` or `3.14`.
+    ///
+    Decimal,
+    ///
+    /// A hexadecimal integer literal, such as `0xDEADbeef`.
+    ///
+    Hex,
+    ///
+    /// `Infinity`
+    ///
+    Infinity,
+    ///
+    /// `NaN`
+    ///
+    NaN,
+}
+
+///
+/// The sign written before a [`Number`].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sign {
+    ///
+    /// No sign, as in `5`.
+    ///
+    Unsigned,
+    ///
+    /// `+`, as in `+5`.
+    ///
+    Plus,
+    ///
+    /// `-`, as in `-5`.
+    ///
+    Minus,
+}
+
+///
+/// A [numeric literal](https://spec.json5.org/#numbers).
+///
+/// Decimal literals may omit the digits either side
+/// of the decimal point, as in `.5` and `5.`, but not both.
+/// The integer part may not have a leading zero, as in the
+/// legacy octal literal `0123`.
+///
+/// A `+` or `-` immediately before a literal is part of the number,
+/// so `-5` and `-Infinity` are single tokens (see [`Number::sign`]).
+/// If anything comes between the sign and the literal, as in `- 5`,
+/// the sign is lexed as a separate [`Minus`](super::tokens::Minus)
+/// or [`Plus`](super::tokens::Plus) punctuator instead.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct Number {
+    span: Span,
+    sign: Sign,
+    kind: NumberKind,
+    decimal_point: bool,
+    exponent: Option<ExponentPart>,
+}
+
+impl Number {
+    ///
+    /// The sign written immediately before this number.
+    ///
+    pub fn sign(&self) -> Sign {
+        self.sign
+    }
+
+    ///
+    /// The form this number was written in.
+    ///
+    pub fn kind(&self) -> NumberKind {
+        self.kind
+    }
+
+    ///
+    /// The text of this number, without its sign.
+    ///
+    fn unsigned_raw<'a>(&self, src: &'a SourceFile) -> &'a str {
+        let text = self.raw(src);
+        match self.sign {
+            Sign::Unsigned => text,
+            Sign::Plus | Sign::Minus => &text[1..],
+        }
+    }
+
+    ///
+    /// The exponent of a decimal literal, such as `e10` in `1e10`.
+    ///
+    pub fn exponent(&self) -> Option<&ExponentPart> {
+        self.exponent.as_ref()
+    }
+
+    ///
+    /// The value of this number, read back from its source.
+    ///
+    /// Finite literals too large for an `f64`, such as `1e400`,
+    /// are an error rather than silently becoming infinite.
+    ///
+    /// ```
+    /// use avjason::{common::SourceFile, lex::{number::Number, Lex}};
+    ///
+    /// let src = SourceFile::dummy_file("0x10");
+    /// let number = Number::lex(&mut src.iter()).unwrap();
+    /// assert_eq!(number.as_f64(&src), Ok(16.0));
+    /// ```
+    ///
+    pub fn as_f64(&self, src: &SourceFile) -> Result<f64, LexError> {
+        let text = self.unsigned_raw(src);
+
+        let value = match self.kind {
+            NumberKind::Infinity => f64::INFINITY,
+            NumberKind::NaN => f64::NAN,
+            NumberKind::Hex => hex_value(&text[2..]),
+            // Rust's float syntax is a superset of JSON5's decimal literals.
+            NumberKind::Decimal => text.parse().expect("lexed a valid decimal literal"),
+        };
+
+        if value.is_infinite() && self.kind != NumberKind::Infinity {
+            return Err(LexError::NumberTooLarge { span: self.span });
+        }
+
+        match self.sign {
+            Sign::Minus => Ok(-value),
+            Sign::Unsigned | Sign::Plus => Ok(value),
+        }
+    }
+
+    ///
+    /// Is this literal written as an integer?
+    ///
+    /// Hex literals are integers. Decimal literals are integers if they
+    /// have no decimal point, and no negative exponent: `1e3` is an
+    /// integer, but `1.0` and `1.5e1` are not, even though their values
+    /// are integral. `Infinity` and `NaN` are not integers.
+    ///
+    pub fn is_integer(&self) -> bool {
+        match self.kind {
+            NumberKind::Hex => true,
+            NumberKind::Decimal => {
+                !self.decimal_point && self.exponent.as_ref().is_none_or(|e| !e.is_negative())
+            }
+            NumberKind::Infinity | NumberKind::NaN => false,
+        }
+    }
+
+    ///
+    /// The value of this number, without its sign,
+    /// if it [is an integer](Number::is_integer) which fits in a `u64`.
+    ///
+    fn magnitude(&self, src: &SourceFile) -> Option<u64> {
+        if !self.is_integer() {
+            return None;
+        }
+
+        let text = self.unsigned_raw(src);
+        match self.kind {
+            NumberKind::Hex => u64::from_str_radix(&text[2..], 16).ok(),
+            _ => {
+                let (mantissa, exponent) = match text.split_once(['e', 'E']) {
+                    Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+                    None => (text, None),
+                };
+
+                // Zero is zero, however large its exponent.
+                let mantissa = match mantissa.parse::<u64>().ok()? {
+                    0 => return Some(0),
+                    mantissa => mantissa,
+                };
+                let exponent = exponent.map_or(Some(0), |e| e.parse::<u32>().ok())?;
+                10u64.checked_pow(exponent)?.checked_mul(mantissa)
+            }
+        }
+    }
+
+    ///
+    /// The value of this number, if it [is an integer](Number::is_integer)
+    /// which fits in an `i64`.
+    ///
+    pub fn as_i64(&self, src: &SourceFile) -> Option<i64> {
+        let magnitude = self.magnitude(src)?;
+        match self.sign {
+            Sign::Minus => 0i64.checked_sub_unsigned(magnitude),
+            Sign::Unsigned | Sign::Plus => i64::try_from(magnitude).ok(),
+        }
+    }
+
+    ///
+    /// The value of this number, if it [is an integer](Number::is_integer)
+    /// which fits in a `u64`.
+    ///
+    /// `-0` is zero, but any other negative number is `None`.
+    ///
+    pub fn as_u64(&self, src: &SourceFile) -> Option<u64> {
+        match (self.sign, self.magnitude(src)?) {
+            (Sign::Minus, 0) => Some(0),
+            (Sign::Minus, _) => None,
+            (Sign::Unsigned | Sign::Plus, magnitude) => Some(magnitude),
+        }
+    }
+
+    ///
+    /// A number written as a word, like `Infinity`.
+    ///
+    fn word(span: Span, kind: NumberKind) -> Self {
+        Self {
+            span,
+            sign: Sign::Unsigned,
+            kind,
+            decimal_point: false,
+            exponent: None,
+        }
+    }
+
+    fn peek_hex(input: &SourceIter) -> bool {
+        input.peek() == Some('0') && matches!(input.peek2(), Some('x' | 'X'))
+    }
+
+    ///
+    /// Consume as many `T`s as possible, returning how many there were.
+    ///
+    fn lex_many<T: Lex>(input: &mut SourceIter) -> Result<usize, LexError> {
+        let mut count = 0;
+        while T::peek(input) {
+            T::lex(input)?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    ///
+    /// `0x` HexDigit+
+    ///
+    fn lex_hex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let start = input.offset();
+        input.next();
+        input.next();
+
+        if Self::lex_many::<HexDigit>(input)? == 0 {
+            return Err(LexError::expected(
+                start..input.offset(),
+                "hexadecimal digits after `0x`",
+            ));
+        }
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+            sign: Sign::Unsigned,
+            kind: NumberKind::Hex,
+            decimal_point: false,
+            exponent: None,
+        })
+    }
+
+    ///
+    /// (DecimalDigit+ (`.` DecimalDigit*)? | `.` DecimalDigit+) ExponentPart?
+    ///
+    fn lex_decimal(input: &mut SourceIter) -> Result<Self, LexError> {
+        let start = input.offset();
+
+        // `Number::peek` ensures there is a digit on at least one side of the `.`
+        let leading_zero = input.peek() == Some('0');
+        if Self::lex_many::<DecimalDigit>(input)? > 1 && leading_zero {
+            return Err(LexError::LegacyOctal {
+                span: Span::new(start, input.offset()),
+            });
+        }
+
+        let decimal_point = input.peek() == Some('.');
+        if decimal_point {
+            input.next();
+            Self::lex_many::<DecimalDigit>(input)?;
+        }
+
+        let exponent = match ExponentPart::peek(input) {
+            true => Some(ExponentPart::lex(input)?),
+            false => None,
+        };
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+            sign: Sign::Unsigned,
+            kind: NumberKind::Decimal,
+            decimal_point,
+            exponent,
+        })
+    }
+
+    ///
+    /// Is the upcoming input a literal, ignoring any sign?
+    ///
+    fn peek_unsigned(input: &SourceIter) -> bool {
+        // A lone `.` is a punctuator, not a number.
+        DecimalDigit::peek(input)
+            || (input.peek() == Some('.') && input.peek2().is_some_and(|ch| ch.is_ascii_digit()))
+            || InfinityKeyword::peek(input)
+            || NaNKeyword::peek(input)
+    }
+
+    fn lex_unsigned(input: &mut SourceIter) -> Result<Self, LexError> {
+        if InfinityKeyword::peek(input) {
+            let span = InfinityKeyword::lex(input)?.span();
+            return Ok(Self::word(span, NumberKind::Infinity));
+        }
+
+        if NaNKeyword::peek(input) {
+            let span = NaNKeyword::lex(input)?.span();
+            return Ok(Self::word(span, NumberKind::NaN));
+        }
+
+        if Self::peek_hex(input) {
+            return Self::lex_hex(input);
+        }
+
+        Self::lex_decimal(input)
+    }
+}
+
+impl Shift for Number {
+    fn shift(&mut self, by: isize) {
+        self.span = self.span.shift(by);
+        if let Some(exponent) = &mut self.exponent {
+            exponent.span = exponent.span.shift(by);
+        }
+    }
+}
+
+impl Lex for Number {
+    fn peek(input: &SourceIter) -> bool {
+        if matches!(input.peek(), Some('+' | '-')) {
+            let mut fork = input.fork();
+            fork.next();
+            return Self::peek_unsigned(&fork);
+        }
+
+        Self::peek_unsigned(input)
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        if !Self::peek(input) {
+            return Err(LexError::unexpected(input));
+        }
+
+        let start = input.offset();
+        let sign = match input.peek() {
+            Some('+') => Sign::Plus,
+            Some('-') => Sign::Minus,
+            _ => Sign::Unsigned,
+        };
+
+        if sign != Sign::Unsigned {
+            input.next();
+        }
+
+        let number = Self::lex_unsigned(input)?;
+
+        // ES5 §7.8.3: a literal may not run straight into an identifier, as in `3in`.
+        if LIdentifier::peek(input) {
+            return Err(LexError::unexpected(input));
+        }
+
+        Ok(Self {
+            span: Span::new(start, number.span.end()),
+            sign,
+            ..number
+        })
+    }
+}
+
+///
+/// The value of a run of hex digits, rounded once to the nearest `f64`.
+///
+fn hex_value(digits: &str) -> f64 {
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return 0.0;
+    }
+
+    // The first 32 digits fit exactly in a `u128`, and any more only scale it.
+    // Those already have more bits than an `f64` keeps, so the rest only matter
+    // to break a tie when rounding, which the lowest bit does just as well.
+    let (head, tail) = digits.split_at(digits.len().min(32));
+    let mut head = u128::from_str_radix(head, 16).expect("lexed hex digits");
+    if tail.bytes().any(|digit| digit != b'0') {
+        head |= 1;
+    }
+
+    let mut value = head as f64;
+    for _ in 0..tail.len() {
+        value *= 16.0;
+        if value.is_infinite() {
+            break;
+        }
+    }
+
+    value
+}
+
+///
+/// The word `Infinity`, but not the start of a longer identifier like `Infinityx`.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned, Lex)]
+#[lex("Infinity", keyword)]
+pub struct InfinityKeyword {
+    span: Span,
+}
+
+///
+/// The word `NaN`, but not the start of a longer identifier like `NaNa`.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned, Lex)]
+#[lex("NaN", keyword)]
+pub struct NaNKeyword {
+    span: Span,
+}
+
+///
+/// The `e` or `E` starting an [`ExponentPart`].
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned, Lex)]
+#[lex('e', ignore_case)]
+pub struct ExponentIndicator {
+    span: Span,
+}
+
+///
+/// The exponent of a decimal literal: `e` or `E`,
+/// an optional sign, then at least one digit.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct ExponentPart {
+    span: Span,
+    negative: bool,
+}
+
+impl ExponentPart {
+    ///
+    /// Is this exponent negative, as in `1e-3`?
+    ///
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+}
+
+impl Lex for ExponentPart {
+    fn peek(input: &SourceIter) -> bool {
+        ExponentIndicator::peek(input)
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        let start = ExponentIndicator::lex(input)?.span().start();
+
+        let negative = input.peek() == Some('-');
+        if matches!(input.peek(), Some('+' | '-')) {
+            input.next();
+        }
+
+        if Number::lex_many::<DecimalDigit>(input)? == 0 {
+            return Err(LexError::expected(start..=start, "digits in exponent"));
+        }
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+            negative,
+        })
+    }
+}
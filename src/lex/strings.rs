@@ -0,0 +1,273 @@
+//!
+//! String literals.
+//!
+
+use alloc::{borrow::Cow, string::String};
+
+use crate::common::{SourceFile, SourceIter, Span, Spanned};
+
+use super::{
+    escapes::{combine_surrogates, EscapeSequence, LineContinuation, UnicodeEscapeSequence},
+    incremental::Shift,
+    Lex, LexError,
+};
+
+///
+/// A [string literal](https://spec.json5.org/#strings),
+/// in either single or double quotes.
+///
+/// A backslash immediately before a line terminator is a line continuation:
+/// the string carries on over the next line, and neither the backslash
+/// nor the line terminator is part of its [value](LString::value).
+/// `\r\n` counts as a single line terminator here.
+///
+/// Otherwise, a string must end on the line it started on,
+/// although U+2028 and U+2029 may appear unescaped.
+/// Control characters (U+0000 to U+001F) must always be escaped.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct LString {
+    span: Span,
+    quote: QuoteStyle,
+}
+
+///
+/// The quotes around a [`LString`].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuoteStyle {
+    ///
+    /// `'single quotes'`
+    ///
+    Single,
+    ///
+    /// `"double quotes"`
+    ///
+    Double,
+}
+
+impl QuoteStyle {
+    ///
+    /// The quote character itself.
+    ///
+    pub fn as_char(&self) -> char {
+        match self {
+            Self::Single => '\'',
+            Self::Double => '"',
+        }
+    }
+}
+
+impl LString {
+    ///
+    /// Which quotes this string was written in.
+    ///
+    /// The closing quote always matches the opening one.
+    ///
+    pub fn quote(&self) -> QuoteStyle {
+        self.quote
+    }
+
+    ///
+    /// The contents of this string, with its quotes removed
+    /// and any escape sequences decoded.
+    ///
+    /// Strings without a backslash are borrowed straight from `src`:
+    /// only escapes and line continuations need a new `String`.
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use avjason::{common::SourceFile, lex::{strings::LString, Lex}};
+    ///
+    /// let src = SourceFile::dummy_file(r"'tab\tstop'");
+    /// let string = LString::lex(&mut src.iter()).unwrap();
+    /// assert_eq!(string.value(&src).unwrap(), "tab\tstop");
+    ///
+    /// let src = SourceFile::dummy_file("'plain'");
+    /// let string = LString::lex(&mut src.iter()).unwrap();
+    /// assert!(matches!(string.value(&src), Ok(Cow::Borrowed("plain"))));
+    /// ```
+    ///
+    pub fn value<'a>(&self, src: &'a SourceFile) -> Result<Cow<'a, str>, LexError> {
+        let bytes = self.span.byte_range(src);
+        // Both quotes are one byte long.
+        let contents = &src.text()[bytes.start + 1..bytes.end - 1];
+        if !contents.contains('\\') {
+            return Ok(Cow::Borrowed(contents));
+        }
+
+        let mut value = String::with_capacity(contents.len());
+        for ch in self.decode(src) {
+            value.push(ch?);
+        }
+
+        Ok(Cow::Owned(value))
+    }
+
+    ///
+    /// The number of characters in this string's [value](LString::value),
+    /// without building it.
+    ///
+    pub fn char_len(&self, src: &SourceFile) -> Result<usize, LexError> {
+        self.decode(src).try_fold(0, |len, ch| ch.map(|_| len + 1))
+    }
+
+    ///
+    /// The length in bytes of this string's [value](LString::value)
+    /// when encoded as UTF-8, without building it.
+    ///
+    pub fn byte_len(&self, src: &SourceFile) -> Result<usize, LexError> {
+        self.decode(src)
+            .try_fold(0, |len, ch| ch.map(|ch| len + ch.len_utf8()))
+    }
+
+    ///
+    /// The characters of this string's value, one at a time.
+    ///
+    fn decode<'a>(&self, src: &'a SourceFile) -> Decode<'a> {
+        Decode {
+            // Both quotes are a single character.
+            input: src.iter_at(self.span.start() + 1),
+            end: self.span.end() - 1,
+        }
+    }
+}
+
+///
+/// Decodes the contents of a [`LString`], character by character.
+///
+struct Decode<'a> {
+    input: SourceIter<'a>,
+    end: usize,
+}
+
+impl Decode<'_> {
+    ///
+    /// Decode a `\u` escape for a surrogate, which began at `backslash`.
+    ///
+    /// Characters outside the Basic Multilingual Plane are written as
+    /// a UTF-16 surrogate pair of escapes, which are combined here.
+    ///
+    fn surrogate_pair(
+        &mut self,
+        backslash: usize,
+        escape: &UnicodeEscapeSequence,
+    ) -> Result<char, LexError> {
+        let unpaired = |end: usize| LexError::UnpairedSurrogate {
+            span: Span::new(backslash, end),
+        };
+
+        let mut fork = self.input.fork();
+        if fork.next().map(|(_, ch)| ch) != Some('\\') || !UnicodeEscapeSequence::peek(&fork) {
+            return Err(unpaired(escape.span().end()));
+        }
+
+        let low = UnicodeEscapeSequence::lex(&mut fork)?;
+        let ch = combine_surrogates(escape.code_unit(), low.code_unit())
+            .ok_or_else(|| unpaired(low.span().end()))?;
+
+        self.input = fork;
+        Ok(ch)
+    }
+}
+
+impl Iterator for Decode<'_> {
+    type Item = Result<char, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.input.offset() >= self.end {
+                return None;
+            }
+
+            // A line continuation is not part of the string's value.
+            if LineContinuation::peek(&self.input) {
+                if let Err(err) = LineContinuation::lex(&mut self.input) {
+                    return Some(Err(err));
+                }
+
+                continue;
+            }
+
+            let (backslash, ch) = self.input.next().expect("still inside the string");
+            if ch != '\\' {
+                return Some(Ok(ch));
+            }
+
+            let escape = match EscapeSequence::lex(&mut self.input) {
+                Ok(escape) => escape,
+                Err(err) => return Some(Err(err)),
+            };
+
+            return Some(match (escape.decode(), escape) {
+                (Some(ch), _) => Ok(ch),
+                (None, EscapeSequence::Unicode(escape)) => self.surrogate_pair(backslash, &escape),
+                (None, _) => unreachable!("only unicode escapes can be surrogates"),
+            });
+        }
+    }
+}
+
+impl Shift for LString {
+    fn shift(&mut self, by: isize) {
+        self.span = self.span.shift(by);
+    }
+}
+
+impl Lex for LString {
+    fn peek(input: &SourceIter) -> bool {
+        matches!(input.peek(), Some('"' | '\''))
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        if !Self::peek(input) {
+            return Err(LexError::unexpected(input));
+        }
+
+        let start = input.offset();
+        let quote = match input.next() {
+            Some((_, '\'')) => QuoteStyle::Single,
+            _ => QuoteStyle::Double,
+        };
+
+        loop {
+            if LineContinuation::peek(input) {
+                LineContinuation::lex(input)?;
+                continue;
+            }
+
+            match input.next() {
+                // The other kind of quote is just a character in this string.
+                Some((_, ch)) if ch == quote.as_char() => break,
+                // A backslash right at the end is just an unterminated string.
+                Some((_, '\\')) if !input.is_eof() => {
+                    EscapeSequence::lex(input)?;
+                }
+                // A string may not run onto the next line without a line continuation.
+                // U+2028 and U+2029 are allowed, for compatibility with JSON.
+                Some((at, '\n' | '\r')) => {
+                    return Err(LexError::UnterminatedString {
+                        span: Span::new(start, at),
+                    })
+                }
+                Some((at, found @ '\0'..='\u{1F}')) => {
+                    return Err(LexError::UnescapedControlChar {
+                        span: Span::new(at, at + 1),
+                        found,
+                    })
+                }
+                Some(_) => {}
+                None => {
+                    return Err(LexError::UnterminatedString {
+                        span: Span::new(start, input.offset()),
+                    })
+                }
+            }
+        }
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+            quote,
+        })
+    }
+}
@@ -0,0 +1,171 @@
+//!
+//! Meaningful tokens: everything other than whitespace and comments.
+//!
+
+use core::fmt;
+
+use crate::common::{Span, Spanned};
+
+use super::{incremental::Shift, Lex};
+
+pub use super::{identifier::LIdentifier, number::Number, strings::LString};
+
+///
+/// A [Token](https://spec.json5.org/#tokens).
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned, Lex)]
+pub enum Token {
+    Identifier(LIdentifier),
+    Punctuator(Punct),
+    String(LString),
+    // Numbers go first, so `.5` is not lexed as a `Dot`.
+    #[lex(priority = 1)]
+    Number(Number),
+}
+
+impl Shift for Token {
+    fn shift(&mut self, by: isize) {
+        match self {
+            Self::Identifier(token) => token.shift(by),
+            Self::Punctuator(token) => token.shift(by),
+            Self::String(token) => token.shift(by),
+            Self::Number(token) => token.shift(by),
+        }
+    }
+}
+
+macro_rules! punctuators {
+    ($($(#[$attr:meta])* $name:ident => $ch:literal),* $(,)?) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Spanned, Lex)]
+            #[lex($ch)]
+            pub struct $name {
+                span: Span,
+            }
+
+            impl Shift for $name {
+                fn shift(&mut self, by: isize) {
+                    self.span = self.span.shift(by);
+                }
+            }
+
+            impl $name {
+                ///
+                /// The character this punctuator is written as.
+                ///
+                pub fn as_char(&self) -> char {
+                    $ch
+                }
+            }
+        )*
+    };
+}
+
+punctuators! {
+    /// `{`
+    OpenBrace => '{',
+    /// `}`
+    CloseBrace => '}',
+    /// `[`
+    OpenBracket => '[',
+    /// `]`
+    CloseBracket => ']',
+    /// `:`
+    Colon => ':',
+    /// `,`
+    Comma => ',',
+    /// `.`
+    Dot => '.',
+    /// `-`
+    Minus => '-',
+    /// `+`
+    Plus => '+',
+}
+
+///
+/// The type of a token, named by how it is written.
+///
+/// Brackets cannot appear on their own inside a macro call,
+/// so they are written as character literals.
+///
+/// ```
+/// use avjason::{lex::tokens::{Colon, LString, OpenBrace}, Token};
+///
+/// let _: Option<Token![:]> = None::<Colon>;
+/// let _: Option<Token!['{']> = None::<OpenBrace>;
+/// let _: Option<Token![str]> = None::<LString>;
+/// ```
+///
+#[macro_export]
+macro_rules! Token {
+    ['{'] => { $crate::lex::tokens::OpenBrace };
+    ['}'] => { $crate::lex::tokens::CloseBrace };
+    ['['] => { $crate::lex::tokens::OpenBracket };
+    [']'] => { $crate::lex::tokens::CloseBracket };
+    [:] => { $crate::lex::tokens::Colon };
+    [,] => { $crate::lex::tokens::Comma };
+    [.] => { $crate::lex::tokens::Dot };
+    [-] => { $crate::lex::tokens::Minus };
+    [+] => { $crate::lex::tokens::Plus };
+    [ident] => { $crate::lex::tokens::LIdentifier };
+    [str] => { $crate::lex::tokens::LString };
+    [num] => { $crate::lex::tokens::Number };
+}
+
+///
+/// A [Punctuator](https://spec.json5.org/#punctuators).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Spanned, Lex)]
+pub enum Punct {
+    OpenBrace(OpenBrace),
+    CloseBrace(CloseBrace),
+    OpenBracket(OpenBracket),
+    CloseBracket(CloseBracket),
+    Colon(Colon),
+    Comma(Comma),
+    Dot(Dot),
+    Minus(Minus),
+    Plus(Plus),
+}
+
+impl Punct {
+    ///
+    /// The character this punctuator is written as.
+    ///
+    pub fn as_char(&self) -> char {
+        match self {
+            Self::OpenBrace(p) => p.as_char(),
+            Self::CloseBrace(p) => p.as_char(),
+            Self::OpenBracket(p) => p.as_char(),
+            Self::CloseBracket(p) => p.as_char(),
+            Self::Colon(p) => p.as_char(),
+            Self::Comma(p) => p.as_char(),
+            Self::Dot(p) => p.as_char(),
+            Self::Minus(p) => p.as_char(),
+            Self::Plus(p) => p.as_char(),
+        }
+    }
+}
+
+impl Shift for Punct {
+    fn shift(&mut self, by: isize) {
+        match self {
+            Self::OpenBrace(p) => p.shift(by),
+            Self::CloseBrace(p) => p.shift(by),
+            Self::OpenBracket(p) => p.shift(by),
+            Self::CloseBracket(p) => p.shift(by),
+            Self::Colon(p) => p.shift(by),
+            Self::Comma(p) => p.shift(by),
+            Self::Dot(p) => p.shift(by),
+            Self::Minus(p) => p.shift(by),
+            Self::Plus(p) => p.shift(by),
+        }
+    }
+}
+
+impl fmt::Display for Punct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
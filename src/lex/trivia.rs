@@ -0,0 +1,213 @@
+//!
+//! Attaching trivia (whitespace, line terminators, and comments)
+//! to the tokens around it.
+//!
+//! This is what formatters need to reprint a document
+//! without losing its comments.
+//!
+
+use alloc::{vec, vec::Vec};
+use core::mem;
+
+use crate::common::{SourceIter, Spanned};
+
+use super::{tokens::Token, InputElement, Lex, LexError};
+
+///
+/// Controls which token the trivia between two tokens is attached to.
+///
+/// The trivia between two tokens is split at its first line terminator:
+/// everything before it is on the *same line* as the preceding token,
+/// everything from it onwards is on its *own line(s)*.
+///
+/// * `same_line_trailing` — same-line trivia is attached as trailing
+///   trivia of the preceding token.
+/// * `leading` — remaining trivia is attached as leading trivia
+///   of the following token.
+/// * `trailing` — remaining trivia not claimed as leading trivia
+///   (because `leading` is off, or there is no following token)
+///   is attached as trailing trivia of the preceding token.
+///
+/// Trivia which no rule claims is discarded.
+///
+/// For example, with the default options:
+/// ```text
+/// [
+///     1, // trailing trivia of `,`
+///     // leading trivia of `2`
+///     2
+/// ]
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommentAttachment {
+    pub leading: bool,
+    pub trailing: bool,
+    pub same_line_trailing: bool,
+}
+
+impl Default for CommentAttachment {
+    fn default() -> Self {
+        Self {
+            leading: true,
+            trailing: true,
+            same_line_trailing: true,
+        }
+    }
+}
+
+///
+/// A token, along with the trivia attached to it.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct TokenWithTrivia {
+    pub leading: Vec<InputElement>,
+    /// The token itself, whose span excludes the trivia around it.
+    #[span]
+    pub token: Token,
+    pub trailing: Vec<InputElement>,
+}
+
+impl TokenWithTrivia {
+    fn new(token: Token) -> Self {
+        Self {
+            leading: vec![],
+            token,
+            trailing: vec![],
+        }
+    }
+}
+
+///
+/// Iterator over the tokens of a source file,
+/// with trivia attached according to a [`CommentAttachment`].
+///
+/// If a token cannot be lexed, the last good token is yielded first,
+/// then the error. Any trivia lexed between the two is dropped:
+/// it belongs to neither, and the iterator ends at the error.
+///
+#[derive(Debug, Clone)]
+pub struct WithTrivia<'a> {
+    input: SourceIter<'a>,
+    attachment: CommentAttachment,
+    previous: Option<TokenWithTrivia>,
+    error: Option<LexError>,
+    done: bool,
+    line_breaks: usize,
+}
+
+impl<'a> WithTrivia<'a> {
+    pub fn new(input: SourceIter<'a>, attachment: CommentAttachment) -> Self {
+        Self {
+            input,
+            attachment,
+            previous: None,
+            error: None,
+            done: false,
+            line_breaks: 0,
+        }
+    }
+
+    ///
+    /// The number of [line terminators](super::line_terminator::LineTerminatorSeq)
+    /// lexed so far, with `\r\n` counting once.
+    ///
+    /// So, once the whole of `"a\nb\nc"` has been lexed, this is 2,
+    /// or 3 if it had a trailing newline.
+    ///
+    /// This is meant for progress reporting: the lexer works one token
+    /// ahead of what has been yielded, and line breaks inside comments
+    /// and strings are not counted. Use a [`LineIndex`](crate::common::LineIndex)
+    /// for accurate locations.
+    ///
+    pub fn line_breaks(&self) -> usize {
+        self.line_breaks
+    }
+
+    ///
+    /// Lex trivia up to (and including) the next token.
+    ///
+    fn lex_gap(&mut self) -> Result<(Vec<InputElement>, Option<Token>), LexError> {
+        let mut trivia = vec![];
+        while !self.input.is_eof() {
+            match InputElement::lex(&mut self.input)? {
+                InputElement::Token(token) => return Ok((trivia, Some(token))),
+                element => {
+                    if let InputElement::LineTerminator(_) = element {
+                        self.line_breaks += 1;
+                    }
+
+                    trivia.push(element);
+                }
+            }
+        }
+
+        Ok((trivia, None))
+    }
+
+    ///
+    /// Share out the trivia between two tokens.
+    ///
+    fn attach(
+        &self,
+        mut trivia: Vec<InputElement>,
+        mut previous: Option<&mut TokenWithTrivia>,
+        next: Option<&mut TokenWithTrivia>,
+    ) {
+        let CommentAttachment {
+            leading,
+            trailing,
+            same_line_trailing,
+        } = self.attachment;
+
+        if let (Some(previous), true) = (previous.as_deref_mut(), same_line_trailing) {
+            let line_end = trivia
+                .iter()
+                .position(|el| matches!(el, InputElement::LineTerminator(_)))
+                .unwrap_or(trivia.len());
+            let own_line = trivia.split_off(line_end);
+            previous
+                .trailing
+                .extend(mem::replace(&mut trivia, own_line));
+        }
+
+        match (next, previous) {
+            (Some(next), _) if leading => next.leading.extend(trivia),
+            (_, Some(previous)) if trailing => previous.trailing.extend(trivia),
+            _ => {}
+        }
+    }
+}
+
+impl<'a> Iterator for WithTrivia<'a> {
+    type Item = Result<TokenWithTrivia, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let (trivia, token) = match self.lex_gap() {
+                Ok(gap) => gap,
+                Err(err) => {
+                    // Finish off the last good token before reporting the error.
+                    self.done = true;
+                    self.error = Some(err);
+                    break;
+                }
+            };
+
+            let mut previous = self.previous.take();
+            let mut next = token.map(TokenWithTrivia::new);
+            self.done = next.is_none();
+            self.attach(trivia, previous.as_mut(), next.as_mut());
+            self.previous = next;
+
+            if previous.is_some() {
+                return previous.map(Ok);
+            }
+        }
+
+        match self.previous.take() {
+            Some(previous) => Some(Ok(previous)),
+            None => self.error.take().map(Err),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//!
+//! Whitespace between tokens.
+//!
+
+use crate::common::{SourceIter, Span, Spanned};
+
+use super::{incremental::Shift, Lex, LexError};
+
+///
+/// Is `ch` a [WhiteSpace](https://262.ecma-international.org/5.1/#sec-7.2) character?
+///
+/// This includes every character in the Unicode `Zs` category.
+///
+pub fn is_whitespace(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{0009}'
+            | '\u{000B}'
+            | '\u{000C}'
+            | '\u{0020}'
+            | '\u{00A0}'
+            | '\u{FEFF}'
+            | '\u{1680}'
+            | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}'
+    )
+}
+
+///
+/// A run of whitespace characters.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub struct WhiteSpace {
+    span: Span,
+}
+
+impl Shift for WhiteSpace {
+    fn shift(&mut self, by: isize) {
+        self.span = self.span.shift(by);
+    }
+}
+
+impl Lex for WhiteSpace {
+    fn peek(input: &SourceIter) -> bool {
+        input.peek().is_some_and(is_whitespace)
+    }
+
+    fn lex(input: &mut SourceIter) -> Result<Self, LexError> {
+        if !Self::peek(input) {
+            return Err(LexError::unexpected(input));
+        }
+
+        let start = input.offset();
+        while Self::peek(input) {
+            input.next();
+        }
+
+        Ok(Self {
+            span: Span::new(start, input.offset()),
+        })
+    }
+}
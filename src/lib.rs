@@ -3,4 +3,26 @@
 //! > A child of the [AvdanOS](https://github.com/Avdan-OS) project.
 //! 
 //! A parser for [JSON5](https://json5.org/).
-//! 
\ No newline at end of file
+//! 
+//! The `std` feature is on by default. Without it, the crate is `no_std`
+//! and only needs `alloc`, but cannot load files or read from readers.
+//!
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+// Lets the derive macros name this crate as `::avjason` from inside it too.
+extern crate self as avjason;
+
+mod collections;
+pub mod common;
+#[cfg(feature = "serde")]
+pub mod de;
+pub mod format;
+pub mod intern;
+pub mod lex;
+pub mod parse;
+#[cfg(feature = "serde")]
+pub mod ser;
+pub mod value;
@@ -0,0 +1,303 @@
+//!
+//! An abstract syntax tree, where every node keeps its span.
+//!
+//! Unlike the [concrete syntax tree](super::cst), whitespace and comments
+//! are dropped, and strings, numbers and keys are decoded as they are parsed.
+//!
+
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{
+    common::{SourceFile, Spanned},
+    lex::{
+        identifier::LIdentifier,
+        tokens::{CloseBrace, CloseBracket, Colon, LString, Number, OpenBrace, OpenBracket, Token},
+        InputElement,
+    },
+    value,
+};
+
+use super::{
+    events::Scalar,
+    parser::{Builder, Parser},
+    ParseError, ParseOptions,
+};
+
+///
+/// Any JSON5 value.
+///
+#[derive(Debug, Clone, PartialEq, Spanned)]
+pub enum Value {
+    Object(Object),
+    Array(Array),
+    Literal(Literal),
+}
+
+impl Value {
+    ///
+    /// The plain [`Value`](value::Value) this node represents, without spans.
+    ///
+    pub fn to_value(&self) -> value::Value {
+        match self {
+            Self::Object(object) => value::Value::Object(
+                object
+                    .members
+                    .iter()
+                    .map(|member| (member.name.clone(), member.value.to_value()))
+                    .collect(),
+            ),
+            Self::Array(array) => {
+                value::Value::Array(array.elements.iter().map(Self::to_value).collect())
+            }
+            Self::Literal(literal) => literal.to_value(),
+        }
+    }
+}
+
+///
+/// `{ key: value, ... }`
+///
+#[derive(Debug, Clone, PartialEq, Spanned)]
+pub struct Object {
+    pub open: OpenBrace,
+    // The braces already cover the members, which may be empty.
+    #[spanned(skip)]
+    pub members: Vec<Member>,
+    pub close: CloseBrace,
+}
+
+impl Object {
+    ///
+    /// The value of the member named `name`, after decoding escapes,
+    /// so `a`, `'a'` and `"\u0061"` all name the same member.
+    ///
+    /// If more than one member has that name, the last one wins.
+    ///
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.members
+            .iter()
+            .rfind(|member| member.name == name)
+            .map(|member| &member.value)
+    }
+}
+
+///
+/// A `key: value` pair in an [`Object`].
+///
+#[derive(Debug, Clone, PartialEq, Spanned)]
+pub struct Member {
+    pub key: Key,
+    ///
+    /// The key, with any escapes decoded.
+    ///
+    #[spanned(skip)]
+    pub name: String,
+    pub colon: Colon,
+    pub value: Value,
+}
+
+///
+/// The key of a [`Member`], as it was written.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub enum Key {
+    Identifier(LIdentifier),
+    String(LString),
+}
+
+///
+/// `[ value, ... ]`
+///
+#[derive(Debug, Clone, PartialEq, Spanned)]
+pub struct Array {
+    pub open: OpenBracket,
+    // The brackets already cover the elements, which may be empty.
+    #[spanned(skip)]
+    pub elements: Vec<Value>,
+    pub close: CloseBracket,
+}
+
+///
+/// A value written as a single token, along with what it decodes to.
+///
+#[derive(Debug, Clone, PartialEq, Spanned)]
+pub enum Literal {
+    Null(LIdentifier),
+    Bool {
+        #[span]
+        token: LIdentifier,
+        value: bool,
+    },
+    String {
+        #[span]
+        token: LString,
+        value: String,
+    },
+    Number {
+        #[span]
+        token: Number,
+        value: f64,
+    },
+}
+
+impl Literal {
+    fn to_value(&self) -> value::Value {
+        match self {
+            Self::Null(_) => value::Value::Null,
+            Self::Bool { value, .. } => value::Value::Bool(*value),
+            Self::String { value, .. } => value::Value::String(value.clone()),
+            Self::Number { value, .. } => value::Value::Number(*value),
+        }
+    }
+}
+
+///
+/// Parse a document into an abstract syntax tree.
+///
+/// ```
+/// use avjason::{common::{SourceFile, Span, Spanned}, parse::parse_ast};
+///
+/// let src = SourceFile::dummy_file("{ a: [1, 2] }");
+/// let ast = parse_ast(&src).unwrap();
+/// assert_eq!(ast.span(), Span::new(0, 13));
+/// ```
+///
+pub fn parse_ast(src: &SourceFile) -> Result<Value, ParseError> {
+    parse_ast_with(src, &ParseOptions::default())
+}
+
+///
+/// Parse a document into an abstract syntax tree, using `options`.
+///
+pub fn parse_ast_with(src: &SourceFile, options: &ParseOptions) -> Result<Value, ParseError> {
+    // Lex everything first, so a token which cannot be lexed
+    // is reported ahead of any syntax error.
+    let tokens: Vec<Token> = src.tokens().collect::<Result<_, _>>()?;
+    let input = tokens
+        .into_iter()
+        .map(|token| Ok(InputElement::Token(token)));
+    let builder = Parser::new(src, options, input, TreeBuilder::default()).document()?;
+    Ok(builder.root.expect("a document has one value"))
+}
+
+///
+/// Builds the tree from what the parser finds.
+///
+#[derive(Default)]
+struct TreeBuilder {
+    ///
+    /// The objects and arrays still being parsed, innermost last.
+    ///
+    open: Vec<Open>,
+    root: Option<Value>,
+}
+
+///
+/// An object or array which is still being parsed.
+///
+enum Open {
+    Object {
+        open: OpenBrace,
+        members: Vec<Member>,
+        // The key and colon of the member whose value is being parsed.
+        key: Option<(Key, String)>,
+        colon: Option<Colon>,
+    },
+    Array {
+        open: OpenBracket,
+        elements: Vec<Value>,
+    },
+}
+
+impl TreeBuilder {
+    ///
+    /// Add a finished value to whatever it is inside of.
+    ///
+    fn value(&mut self, value: Value) {
+        match self.open.last_mut() {
+            None => self.root = Some(value),
+            Some(Open::Array { elements, .. }) => elements.push(value),
+            Some(Open::Object {
+                members,
+                key,
+                colon,
+                ..
+            }) => {
+                let (key, name) = key.take().expect("a member's value follows its key");
+                let colon = colon.take().expect("a member's value follows its `:`");
+                members.push(Member {
+                    key,
+                    name,
+                    colon,
+                    value,
+                });
+            }
+        }
+    }
+}
+
+impl Builder for TreeBuilder {
+    fn begin_object(&mut self, open: OpenBrace) {
+        self.open.push(Open::Object {
+            open,
+            members: vec![],
+            key: None,
+            colon: None,
+        });
+    }
+
+    fn key(&mut self, key: Key, name: &str) {
+        if let Some(Open::Object { key: pending, .. }) = self.open.last_mut() {
+            *pending = Some((key, name.into()));
+        }
+    }
+
+    fn colon(&mut self, colon: Colon) {
+        if let Some(Open::Object { colon: pending, .. }) = self.open.last_mut() {
+            *pending = Some(colon);
+        }
+    }
+
+    fn end_object(&mut self, close: CloseBrace) {
+        let Some(Open::Object { open, members, .. }) = self.open.pop() else {
+            unreachable!("objects are closed where they were opened");
+        };
+        self.value(Value::Object(Object {
+            open,
+            members,
+            close,
+        }));
+    }
+
+    fn begin_array(&mut self, open: OpenBracket) {
+        self.open.push(Open::Array {
+            open,
+            elements: vec![],
+        });
+    }
+
+    fn end_array(&mut self, close: CloseBracket) {
+        let Some(Open::Array { open, elements }) = self.open.pop() else {
+            unreachable!("arrays are closed where they were opened");
+        };
+        self.value(Value::Array(Array {
+            open,
+            elements,
+            close,
+        }));
+    }
+
+    fn scalar(&mut self, token: Token, value: Scalar<'_>) {
+        let literal = match (token, value) {
+            (Token::Identifier(token), Scalar::Null) => Literal::Null(token),
+            (Token::Identifier(token), Scalar::Bool(value)) => Literal::Bool { token, value },
+            (Token::String(token), Scalar::String(value)) => Literal::String {
+                token,
+                value: value.into(),
+            },
+            (Token::Number(token), Scalar::Number(value)) => Literal::Number { token, value },
+            _ => unreachable!("scalars are decoded from their own token"),
+        };
+        self.value(Value::Literal(literal));
+    }
+}
@@ -0,0 +1,398 @@
+//!
+//! A lossless concrete syntax tree.
+//!
+//! Unlike [`Value`](crate::value::Value), the tree keeps every
+//! input element of the source — including whitespace and comments —
+//! in order, so printing it reproduces the source exactly.
+//! Edits to the tree leave the surrounding trivia untouched.
+//!
+
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::{
+    common::{SourceFile, Spanned},
+    lex::{
+        identifier::LIdentifier,
+        tokens::{CloseBrace, CloseBracket, Colon, Comma, LString, OpenBrace, OpenBracket, Token},
+        InputElement, Lex, TokensWithTrivia,
+    },
+};
+
+use super::{
+    ast::Key,
+    events::Scalar,
+    parser::{Builder, Parser},
+    DuplicateKeys, ParseError, ParseOptions,
+};
+
+///
+/// What sort of input element a [`Leaf`] holds.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeafKind {
+    WhiteSpace,
+    LineTerminator,
+    Comment,
+    Identifier,
+    Punctuator,
+    String,
+    Number,
+}
+
+impl LeafKind {
+    fn of(element: &InputElement) -> Self {
+        match element {
+            InputElement::WhiteSpace(_) => Self::WhiteSpace,
+            InputElement::LineTerminator(_) => Self::LineTerminator,
+            InputElement::Comment(_) => Self::Comment,
+            InputElement::Token(Token::Identifier(_)) => Self::Identifier,
+            InputElement::Token(Token::Punctuator(_)) => Self::Punctuator,
+            InputElement::Token(Token::String(_)) => Self::String,
+            InputElement::Token(Token::Number(_)) => Self::Number,
+        }
+    }
+
+    ///
+    /// Is this whitespace, a line terminator, or a comment?
+    ///
+    pub fn is_trivia(&self) -> bool {
+        matches!(
+            self,
+            Self::WhiteSpace | Self::LineTerminator | Self::Comment
+        )
+    }
+}
+
+///
+/// A single input element, with its original text.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaf {
+    kind: LeafKind,
+    text: String,
+}
+
+impl Leaf {
+    pub fn kind(&self) -> LeafKind {
+        self.kind
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+///
+/// A node of the tree.
+///
+/// Trivia is kept as [`Node::Leaf`] children of the innermost
+/// object, array, or member it appears in.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Leaf(Leaf),
+    Object(Vec<Node>),
+    Array(Vec<Node>),
+    ///
+    /// An object member: its key, `:`, and value,
+    /// along with any trivia between them.
+    ///
+    Member(Vec<Node>),
+}
+
+impl Node {
+    ///
+    /// The nodes inside this one, in order, including any brackets,
+    /// commas and trivia. A leaf has none.
+    ///
+    pub fn children(&self) -> &[Node] {
+        match self {
+            Self::Leaf(_) => &[],
+            Self::Object(children) | Self::Array(children) | Self::Member(children) => children,
+        }
+    }
+
+    fn is_trivia(&self) -> bool {
+        matches!(self, Self::Leaf(leaf) if leaf.kind.is_trivia())
+    }
+
+    ///
+    /// The value of a member.
+    ///
+    fn member_value_mut(&mut self) -> Option<&mut Node> {
+        match self {
+            Self::Member(children) => children.iter_mut().rfind(|node| !node.is_trivia()),
+            _ => None,
+        }
+    }
+
+    ///
+    /// The key of a member, without quotes and with any escapes decoded.
+    ///
+    fn member_key(&self) -> Option<Cow<'_, str>> {
+        let Self::Member(children) = self else {
+            return None;
+        };
+        let Some(Self::Leaf(Leaf { kind, text })) = children.first() else {
+            return None;
+        };
+
+        if !text.contains('\\') {
+            return match kind {
+                LeafKind::Identifier => Some(Cow::Borrowed(text)),
+                LeafKind::String => Some(Cow::Borrowed(&text[1..text.len() - 1])),
+                _ => None,
+            };
+        }
+
+        // Only keys with escapes need lexing again, to decode them.
+        let src = SourceFile::dummy_file(text.as_str());
+        let name = match kind {
+            LeafKind::Identifier => LIdentifier::lex(&mut src.iter()).ok()?.name(&src),
+            LeafKind::String => LString::lex(&mut src.iter()).ok()?.value(&src).ok()?,
+            _ => return None,
+        };
+        Some(Cow::Owned(name.into_owned()))
+    }
+
+    ///
+    /// The value of the member with key `key`, if this is an object.
+    ///
+    /// Keys are compared after decoding escapes, so `a`, `'a'` and
+    /// `"\u0061"` all name the same member. If more than one member
+    /// has that key, the last one wins.
+    ///
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Node> {
+        match self {
+            Self::Object(children) => children
+                .iter_mut()
+                .rfind(|node| node.member_key().as_deref() == Some(key))?
+                .member_value_mut(),
+            _ => None,
+        }
+    }
+
+    ///
+    /// The `index`th element, if this is an array.
+    ///
+    pub fn index_mut(&mut self, index: usize) -> Option<&mut Node> {
+        match self {
+            Self::Array(children) => children
+                .iter_mut()
+                .filter(|node| {
+                    !node.is_trivia()
+                        && !matches!(
+                            node,
+                            Self::Leaf(Leaf {
+                                kind: LeafKind::Punctuator,
+                                ..
+                            })
+                        )
+                })
+                .nth(index),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Replace this node with the value parsed from `source`.
+    ///
+    /// Trivia around this node belongs to its parent, so is kept.
+    /// Whitespace and comments before or after the value in `source`
+    /// are skipped, so `" 2 "` replaces this node with just `2`.
+    ///
+    pub fn replace_with(&mut self, source: &str) -> Result<(), ParseError> {
+        let cst = parse_cst(&SourceFile::dummy_file(source))?;
+        *self = cst
+            .children
+            .into_iter()
+            .find(|node| !node.is_trivia())
+            .expect("a document has exactly one value");
+        Ok(())
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Leaf(leaf) => f.write_str(&leaf.text),
+            _ => self.children().iter().try_for_each(|node| node.fmt(f)),
+        }
+    }
+}
+
+///
+/// A whole JSON5 document, as a lossless syntax tree.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cst {
+    children: Vec<Node>,
+}
+
+impl Cst {
+    ///
+    /// Every node of the document: its value,
+    /// and any trivia before or after it.
+    ///
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+
+    ///
+    /// The top-level value of the document.
+    ///
+    pub fn value(&self) -> &Node {
+        self.children
+            .iter()
+            .find(|node| !node.is_trivia())
+            .expect("a document has exactly one value")
+    }
+
+    ///
+    /// The top-level value of the document, for editing.
+    ///
+    pub fn value_mut(&mut self) -> &mut Node {
+        self.children
+            .iter_mut()
+            .find(|node| !node.is_trivia())
+            .expect("a document has exactly one value")
+    }
+}
+
+impl fmt::Display for Cst {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.children.iter().try_for_each(|node| node.fmt(f))
+    }
+}
+
+///
+/// Parse a document into a lossless syntax tree.
+///
+/// ```
+/// use avjason::{common::SourceFile, parse::parse_cst};
+///
+/// let src = SourceFile::dummy_file("{ a: 1, /* keep me */ b: 2 }");
+/// let mut cst = parse_cst(&src).unwrap();
+/// cst.value_mut().get_mut("b").unwrap().replace_with("[3]").unwrap();
+/// assert_eq!(cst.to_string(), "{ a: 1, /* keep me */ b: [3] }");
+/// ```
+///
+pub fn parse_cst(src: &SourceFile) -> Result<Cst, ParseError> {
+    parse_cst_with(src, &ParseOptions::default())
+}
+
+///
+/// Parse a document into a concrete syntax tree, using `options`.
+///
+pub fn parse_cst_with(src: &SourceFile, options: &ParseOptions) -> Result<Cst, ParseError> {
+    // Every member is kept, whatever the options say about duplicates.
+    let options = ParseOptions {
+        duplicate_keys: DuplicateKeys::Allow,
+        ..options.clone()
+    };
+
+    // Lex everything first, as for the other trees, keeping any
+    // byte order mark as whitespace, so the tree is lossless.
+    let elements: Vec<_> = TokensWithTrivia::new(src.iter_at(0)).collect::<Result<_, _>>()?;
+    let builder = TreeBuilder {
+        src,
+        open: vec![vec![]],
+    };
+    let mut builder =
+        Parser::new(src, &options, elements.into_iter().map(Ok), builder).document()?;
+    Ok(Cst {
+        children: builder.open.pop().expect("the document is never closed"),
+    })
+}
+
+///
+/// Builds the tree from what the parser finds, trivia and all.
+///
+struct TreeBuilder<'a> {
+    src: &'a SourceFile,
+    ///
+    /// The children of the document, then of each object, array,
+    /// and member still being parsed, innermost last.
+    ///
+    open: Vec<Vec<Node>>,
+}
+
+impl TreeBuilder<'_> {
+    fn push(&mut self, node: Node) {
+        self.open
+            .last_mut()
+            .expect("the document is never closed")
+            .push(node);
+    }
+
+    fn leaf(&mut self, kind: LeafKind, element: &impl Spanned) {
+        let text = element.raw(self.src).to_string();
+        self.push(Node::Leaf(Leaf { kind, text }));
+    }
+
+    ///
+    /// Finish the innermost object, array, or member.
+    ///
+    fn close(&mut self, node: fn(Vec<Node>) -> Node) {
+        let children = self.open.pop().expect("only opened nodes are closed");
+        self.push(node(children));
+    }
+}
+
+impl Builder for TreeBuilder<'_> {
+    fn trivia(&mut self, element: InputElement) {
+        self.leaf(LeafKind::of(&element), &element);
+    }
+
+    fn begin_object(&mut self, open: OpenBrace) {
+        self.open.push(vec![]);
+        self.leaf(LeafKind::Punctuator, &open);
+    }
+
+    fn key(&mut self, key: Key, _name: &str) {
+        self.open.push(vec![]);
+        let kind = match key {
+            Key::Identifier(_) => LeafKind::Identifier,
+            Key::String(_) => LeafKind::String,
+        };
+        self.leaf(kind, &key);
+    }
+
+    fn colon(&mut self, colon: Colon) {
+        self.leaf(LeafKind::Punctuator, &colon);
+    }
+
+    fn end_member(&mut self) {
+        self.close(Node::Member);
+    }
+
+    fn comma(&mut self, comma: Comma) {
+        self.leaf(LeafKind::Punctuator, &comma);
+    }
+
+    fn end_object(&mut self, close: CloseBrace) {
+        self.leaf(LeafKind::Punctuator, &close);
+        self.close(Node::Object);
+    }
+
+    fn begin_array(&mut self, open: OpenBracket) {
+        self.open.push(vec![]);
+        self.leaf(LeafKind::Punctuator, &open);
+    }
+
+    fn end_array(&mut self, close: CloseBracket) {
+        self.leaf(LeafKind::Punctuator, &close);
+        self.close(Node::Array);
+    }
+
+    fn scalar(&mut self, token: Token, _value: Scalar<'_>) {
+        let element = InputElement::Token(token);
+        self.leaf(LeafKind::of(&element), &element);
+    }
+}
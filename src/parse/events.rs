@@ -0,0 +1,151 @@
+//!
+//! Streaming parse events, for consumers which would rather react to a
+//! document as it is read than build a tree of it.
+//!
+//! Tokens are lexed one at a time as the parser needs them, so nothing
+//! the size of the document is allocated.
+//!
+
+use crate::{
+    common::{SourceFile, Span, Spanned},
+    lex::{
+        tokens::{CloseBrace, CloseBracket, OpenBrace, OpenBracket, Token},
+        InputElement,
+    },
+};
+
+use super::{
+    ast::Key,
+    parser::{Builder, Parser},
+    ParseError, ParseOptions,
+};
+
+///
+/// Callbacks for each part of a document, in the order they are written.
+///
+/// Every callback does nothing by default, so a visitor only needs
+/// to implement the ones it is interested in.
+///
+pub trait Visitor {
+    ///
+    /// The opening `{` of an object.
+    ///
+    fn begin_object(&mut self, _open: Span) {}
+
+    ///
+    /// The key of an object member, with any escapes decoded.
+    /// Its value follows as the next event.
+    ///
+    fn key(&mut self, _name: &str, _span: Span) {}
+
+    ///
+    /// The closing `}` of an object.
+    ///
+    fn end_object(&mut self, _close: Span) {}
+
+    ///
+    /// The opening `[` of an array.
+    ///
+    fn begin_array(&mut self, _open: Span) {}
+
+    ///
+    /// The closing `]` of an array.
+    ///
+    fn end_array(&mut self, _close: Span) {}
+
+    ///
+    /// A value written as a single token.
+    ///
+    fn scalar(&mut self, _value: Scalar<'_>, _span: Span) {}
+}
+
+///
+/// A decoded value written as a single token.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scalar<'a> {
+    Null,
+    Bool(bool),
+    String(&'a str),
+    Number(f64),
+}
+
+///
+/// Parse a document, calling `visitor` for each part of it.
+///
+/// Events are delivered as soon as they are parsed, so if the document
+/// turns out to be invalid, `visitor` will already have seen everything
+/// before the error. For the same reason, a token which cannot be lexed
+/// is only reported if the parser reaches it before any other error.
+///
+/// ```
+/// use avjason::{
+///     common::{SourceFile, Span},
+///     parse::{events::Visitor, parse_events},
+/// };
+///
+/// #[derive(Default)]
+/// struct Keys(Vec<String>);
+///
+/// impl Visitor for Keys {
+///     fn key(&mut self, name: &str, _span: Span) {
+///         self.0.push(name.to_string());
+///     }
+/// }
+///
+/// let src = SourceFile::dummy_file("{ a: 1, 'b': { c: [] } }");
+/// let mut keys = Keys::default();
+/// parse_events(&src, &mut keys).unwrap();
+/// assert_eq!(keys.0, ["a", "b", "c"]);
+/// ```
+///
+pub fn parse_events(src: &SourceFile, visitor: &mut impl Visitor) -> Result<(), ParseError> {
+    parse_events_with(src, &ParseOptions::default(), visitor)
+}
+
+///
+/// Parse a document, calling `visitor` for each part of it, using `options`.
+///
+/// With [`DuplicateKeys::FirstWins`], no events are delivered for
+/// the later members with a key which has already been seen.
+///
+pub fn parse_events_with(
+    src: &SourceFile,
+    options: &ParseOptions,
+    visitor: &mut impl Visitor,
+) -> Result<(), ParseError> {
+    let tokens = src.tokens().map(|token| token.map(InputElement::Token));
+    Parser::new(src, options, tokens, Events(visitor)).document()?;
+    Ok(())
+}
+
+///
+/// Passes on what the parser finds to a [`Visitor`].
+///
+struct Events<'a, V>(&'a mut V);
+
+impl<V: Visitor> Builder for Events<'_, V> {
+    fn begin_object(&mut self, open: OpenBrace) {
+        self.0.begin_object(open.span());
+    }
+
+    fn key(&mut self, key: Key, name: &str) {
+        self.0.key(name, key.span());
+    }
+
+    fn end_object(&mut self, close: CloseBrace) {
+        self.0.end_object(close.span());
+    }
+
+    fn begin_array(&mut self, open: OpenBracket) {
+        self.0.begin_array(open.span());
+    }
+
+    fn end_array(&mut self, close: CloseBracket) {
+        self.0.end_array(close.span());
+    }
+
+    fn scalar(&mut self, token: Token, value: Scalar<'_>) {
+        self.0.scalar(value, token.span());
+    }
+}
@@ -0,0 +1,67 @@
+//!
+//! Parsing a document straight to a [`serde_json::Value`].
+//!
+
+use serde_json::{Map, Number as JsonNumber, Value as JsonValue};
+
+use crate::common::SourceFile;
+
+use super::{
+    ast::{parse_ast, Literal, Value},
+    ParseError,
+};
+
+///
+/// Parse a document into a [`serde_json::Value`].
+///
+/// Integers become `u64` or `i64` numbers when they fit, and any other
+/// number an `f64`. JSON cannot represent `Infinity` or `NaN`, so, as
+/// with `serde_json`'s own conversions from `f64`, they become `null`.
+///
+/// If an object has the same key more than once, the last value wins.
+///
+/// ```
+/// use avjason::{common::SourceFile, parse::to_serde_json};
+/// use serde_json::json;
+///
+/// let src = SourceFile::dummy_file("{ big: 0xFFFFFFFFFFFFFFFF, half: .5, nan: NaN }");
+/// assert_eq!(
+///     to_serde_json(&src).unwrap(),
+///     json!({ "big": u64::MAX, "half": 0.5, "nan": null }),
+/// );
+/// ```
+///
+pub fn to_serde_json(src: &SourceFile) -> Result<JsonValue, ParseError> {
+    Ok(convert(src, &parse_ast(src)?))
+}
+
+fn convert(src: &SourceFile, value: &Value) -> JsonValue {
+    match value {
+        Value::Object(object) => JsonValue::Object(
+            object
+                .members
+                .iter()
+                .map(|member| (member.name.clone(), convert(src, &member.value)))
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(array) => {
+            JsonValue::Array(array.elements.iter().map(|el| convert(src, el)).collect())
+        }
+        Value::Literal(Literal::Null(_)) => JsonValue::Null,
+        Value::Literal(Literal::Bool { value, .. }) => JsonValue::Bool(*value),
+        Value::Literal(Literal::String { value, .. }) => JsonValue::String(value.clone()),
+        Value::Literal(Literal::Number { token, value }) => {
+            // `-0` is an integer, but only a float can keep its sign.
+            let negative_zero = *value == 0.0 && value.is_sign_negative();
+            if negative_zero {
+                JsonNumber::from_f64(*value).map_or(JsonValue::Null, JsonValue::Number)
+            } else if let Some(n) = token.as_u64(src) {
+                JsonValue::Number(n.into())
+            } else if let Some(n) = token.as_i64(src) {
+                JsonValue::Number(n.into())
+            } else {
+                JsonNumber::from_f64(*value).map_or(JsonValue::Null, JsonValue::Number)
+            }
+        }
+    }
+}
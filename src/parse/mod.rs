@@ -0,0 +1,155 @@
+//!
+//! Syntactic grammar of JSON5.
+//!
+//! See the [JSON5 specification](https://spec.json5.org/#syntactic-grammar).
+//!
+
+pub mod ast;
+pub mod cst;
+pub mod events;
+#[cfg(feature = "serde_json")]
+mod json;
+mod parser;
+mod value;
+
+use alloc::{format, string::String};
+
+use crate::{
+    common::{Span, Spanned},
+    lex::LexError,
+};
+
+pub use self::{
+    ast::{parse_ast, parse_ast_with},
+    cst::{parse_cst, parse_cst_with},
+    events::{parse_events, parse_events_with},
+    value::{parse, parse_with},
+};
+
+#[cfg(feature = "serde_json")]
+pub use self::json::to_serde_json;
+
+///
+/// Options for how a document is parsed.
+///
+/// ```
+/// use avjason::parse::ParseOptions;
+///
+/// let options = ParseOptions {
+///     max_depth: 16,
+///     ..ParseOptions::default()
+/// };
+/// # let _ = options;
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    ///
+    /// How deeply objects and arrays may be nested, counting the
+    /// outermost one as depth 1. Anything deeper is a
+    /// [`ParseError::DepthExceeded`], rather than a stack overflow.
+    ///
+    /// Defaults to 128.
+    ///
+    pub max_depth: usize,
+
+    ///
+    /// What to do when an object has the same key more than once.
+    ///
+    /// Keys are compared after decoding escapes, so `a` and `'\x61'`
+    /// are the same key. This does not affect the
+    /// [concrete syntax tree](cst), which always keeps every member.
+    ///
+    pub duplicate_keys: DuplicateKeys,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            duplicate_keys: DuplicateKeys::default(),
+        }
+    }
+}
+
+///
+/// How to handle an object with the same key more than once,
+/// as in `{ a: 1, a: 2 }`.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    ///
+    /// Keep every member: the last one wins when looking up the key,
+    /// as JSON5 specifies.
+    ///
+    #[default]
+    Allow,
+
+    ///
+    /// Fail with a [`ParseError::DuplicateKey`].
+    ///
+    Error,
+
+    ///
+    /// Keep only the first member with each key, dropping the rest.
+    ///
+    FirstWins,
+}
+
+///
+/// Something went wrong whilst parsing.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
+pub enum ParseError {
+    ///
+    /// The document does not follow the grammar,
+    /// or one of its tokens could not be lexed.
+    ///
+    Syntax { span: Span, message: String },
+
+    ///
+    /// An object or array nested deeper than [`ParseOptions::max_depth`].
+    /// The span is its opening bracket.
+    ///
+    DepthExceeded { span: Span },
+
+    ///
+    /// A key used more than once in the same object, when
+    /// [`ParseOptions::duplicate_keys`] is [`DuplicateKeys::Error`].
+    /// The span is the second use, and `first` is the first.
+    ///
+    DuplicateKey {
+        span: Span,
+        first: Span,
+        name: String,
+    },
+}
+
+impl ParseError {
+    ///
+    /// A [syntax error](ParseError::Syntax).
+    ///
+    pub fn new(span: impl Into<Span>, message: impl Into<String>) -> Self {
+        Self::Syntax {
+            span: span.into(),
+            message: message.into(),
+        }
+    }
+
+    ///
+    /// A description of the problem.
+    ///
+    pub fn message(&self) -> String {
+        match self {
+            Self::Syntax { message, .. } => message.clone(),
+            Self::DepthExceeded { .. } => "objects and arrays are nested too deeply".into(),
+            Self::DuplicateKey { name, .. } => format!("duplicate key {name:?}"),
+        }
+    }
+}
+
+impl From<LexError> for ParseError {
+    fn from(err: LexError) -> Self {
+        Self::new(err.span(), err.message())
+    }
+}
@@ -0,0 +1,358 @@
+//!
+//! The grammar shared by every kind of parse.
+//!
+//! The [abstract](super::ast) and [concrete](super::cst) syntax trees
+//! and [parse events](super::events) all read documents the same way,
+//! and only differ in what they make of them: see [`Builder`].
+//!
+
+use alloc::string::String;
+
+use crate::{
+    collections::HashMap,
+    common::{SourceFile, Span, Spanned},
+    lex::{
+        identifier::Keyword,
+        tokens::{CloseBrace, CloseBracket, Colon, Comma, OpenBrace, OpenBracket, Punct, Token},
+        InputElement, LexError,
+    },
+};
+
+use super::{ast::Key, events::Scalar, DuplicateKeys, ParseError, ParseOptions};
+
+///
+/// What a [`Parser`] finds, told to whatever it is building,
+/// in the order it is written.
+///
+/// An object member is its key, `:`, and value, then
+/// [`end_member`](Builder::end_member).
+///
+pub(super) trait Builder {
+    ///
+    /// Whitespace, a line terminator, or a comment.
+    ///
+    fn trivia(&mut self, _element: InputElement) {}
+
+    fn begin_object(&mut self, open: OpenBrace);
+
+    ///
+    /// The key of an object member, and its name with any escapes decoded.
+    ///
+    fn key(&mut self, key: Key, name: &str);
+
+    fn colon(&mut self, _colon: Colon) {}
+
+    ///
+    /// The end of an object member, straight after its value.
+    ///
+    fn end_member(&mut self) {}
+
+    ///
+    /// A `,` after an element of an array, or a member of an object.
+    ///
+    fn comma(&mut self, _comma: Comma) {}
+
+    fn end_object(&mut self, close: CloseBrace);
+
+    fn begin_array(&mut self, open: OpenBracket);
+
+    fn end_array(&mut self, close: CloseBracket);
+
+    ///
+    /// A value written as a single token, along with what it decodes to.
+    ///
+    fn scalar(&mut self, token: Token, value: Scalar<'_>);
+}
+
+///
+/// Parses a document from `input`, telling a [`Builder`] about each part of it.
+///
+pub(super) struct Parser<'a, I, B> {
+    src: &'a SourceFile,
+    options: &'a ParseOptions,
+    input: I,
+    ///
+    /// The next token, once it has been lexed.
+    ///
+    next: Option<Token>,
+    ///
+    /// Has everything up to `next` been lexed?
+    ///
+    peeked: bool,
+    builder: B,
+    depth: usize,
+    ///
+    /// How many skipped duplicate members we are inside of.
+    ///
+    muted: usize,
+}
+
+impl<'a, I, B> Parser<'a, I, B>
+where
+    I: Iterator<Item = Result<InputElement, LexError>>,
+    B: Builder,
+{
+    pub(super) fn new(
+        src: &'a SourceFile,
+        options: &'a ParseOptions,
+        input: I,
+        builder: B,
+    ) -> Self {
+        Self {
+            src,
+            options,
+            input,
+            next: None,
+            peeked: false,
+            builder,
+            depth: 0,
+            muted: 0,
+        }
+    }
+
+    ///
+    /// Parse a whole document: one value, with nothing but trivia
+    /// around it. Then hand back the builder, to take what it built.
+    ///
+    pub(super) fn document(mut self) -> Result<B, ParseError> {
+        self.value()?;
+        self.lookahead()?;
+        match self.peek() {
+            Some(_) => Err(self.error("expected end of input")),
+            None => Ok(self.builder),
+        }
+    }
+
+    ///
+    /// Lex the next token, unless that has already been done,
+    /// passing on any trivia before it.
+    ///
+    fn lookahead(&mut self) -> Result<(), ParseError> {
+        while !self.peeked {
+            match self.input.next().transpose()? {
+                Some(InputElement::Token(token)) => {
+                    self.next = Some(token);
+                    self.peeked = true;
+                }
+                Some(trivia) => self.emit(|builder| builder.trivia(trivia)),
+                None => self.peeked = true,
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// The next token, which must have been [lexed](Parser::lookahead) already.
+    ///
+    fn peek(&self) -> Option<&Token> {
+        self.next.as_ref()
+    }
+
+    ///
+    /// The span of the next token, or the end of the input.
+    ///
+    fn here(&self) -> Span {
+        match self.peek() {
+            Some(token) => token.span(),
+            None => Span::empty(self.src.char_len()),
+        }
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        ParseError::new(self.here(), message)
+    }
+
+    ///
+    /// Consume the next token, which has already been peeked.
+    ///
+    fn bump(&mut self) -> Token {
+        self.peeked = false;
+        self.next.take().expect("only peeked tokens are consumed")
+    }
+
+    ///
+    /// Tell the builder about something, unless it is part of a skipped
+    /// duplicate member.
+    ///
+    fn emit(&mut self, event: impl FnOnce(&mut B)) {
+        if self.muted == 0 {
+            event(&mut self.builder);
+        }
+    }
+
+    ///
+    /// Parse an object or array with `parse`, one level deeper.
+    ///
+    fn nested(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<(), ParseError>,
+    ) -> Result<(), ParseError> {
+        if self.depth >= self.options.max_depth {
+            return Err(ParseError::DepthExceeded { span: self.here() });
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn value(&mut self) -> Result<(), ParseError> {
+        self.lookahead()?;
+        let src = self.src;
+        let string;
+        let value = match self.peek() {
+            Some(Token::Punctuator(Punct::OpenBrace(_))) => return self.nested(Self::object),
+            Some(Token::Punctuator(Punct::OpenBracket(_))) => return self.nested(Self::array),
+            Some(Token::Identifier(ident)) => match ident.as_keyword(src) {
+                Some(Keyword::Null) => Scalar::Null,
+                Some(keyword) => Scalar::Bool(keyword == Keyword::True),
+                None => return Err(self.error("expected a value")),
+            },
+            Some(Token::String(token)) => {
+                string = token.value(src)?;
+                Scalar::String(&string)
+            }
+            Some(Token::Number(number)) => Scalar::Number(number.as_f64(src)?),
+            Some(Token::Punctuator(_)) | None => return Err(self.error("expected a value")),
+        };
+
+        let token = self.bump();
+        self.emit(|builder| builder.scalar(token, value));
+        Ok(())
+    }
+
+    ///
+    /// Parse a comma-separated list of `item`s, up to (but not including) `close`.
+    ///
+    /// There may be a single trailing comma after the last item,
+    /// but no comma without an item before it.
+    ///
+    fn list(
+        &mut self,
+        is_close: fn(&Punct) -> bool,
+        mut item: impl FnMut(&mut Self) -> Result<(), ParseError>,
+    ) -> Result<(), ParseError> {
+        loop {
+            self.lookahead()?;
+            match self.peek() {
+                Some(Token::Punctuator(punct)) if is_close(punct) => return Ok(()),
+                Some(Token::Punctuator(Punct::Comma(_))) => {
+                    return Err(self.error("unexpected `,` without an item before it"))
+                }
+                _ => {}
+            }
+
+            item(self)?;
+            self.lookahead()?;
+            match self.peek() {
+                Some(&Token::Punctuator(Punct::Comma(comma))) => {
+                    self.bump();
+                    self.emit(|builder| builder.comma(comma));
+                }
+                Some(Token::Punctuator(punct)) if is_close(punct) => {}
+                _ => return Err(self.error("expected `,` or a closing bracket")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<(), ParseError> {
+        let Token::Punctuator(Punct::OpenBrace(open)) = self.bump() else {
+            unreachable!("objects start at a peeked `{{`");
+        };
+        self.emit(|builder| builder.begin_object(open));
+
+        // The first span of each key, to apply `ParseOptions::duplicate_keys`.
+        let mut seen = match self.options.duplicate_keys {
+            DuplicateKeys::Allow => None,
+            DuplicateKeys::Error | DuplicateKeys::FirstWins => Some(HashMap::new()),
+        };
+        self.list(
+            |punct| matches!(punct, Punct::CloseBrace(_)),
+            |parser| parser.member(&mut seen),
+        )?;
+
+        let Token::Punctuator(Punct::CloseBrace(close)) = self.bump() else {
+            unreachable!("`list` stops at a peeked `}}`");
+        };
+        self.emit(|builder| builder.end_object(close));
+        Ok(())
+    }
+
+    fn member(&mut self, seen: &mut Option<HashMap<String, Span>>) -> Result<(), ParseError> {
+        self.lookahead()?;
+        let src = self.src;
+        let name = match self.peek() {
+            Some(Token::Identifier(ident)) => ident.name(src),
+            Some(Token::String(string)) => string.value(src)?,
+            _ => {
+                return Err(
+                    self.error("expected an object key, which must be an identifier or a string")
+                )
+            }
+        };
+        let key = match self.bump() {
+            Token::Identifier(ident) => Key::Identifier(ident),
+            Token::String(string) => Key::String(string),
+            _ => unreachable!("keys are peeked above"),
+        };
+
+        let skip = match seen {
+            None => false,
+            Some(seen) => match seen.get(name.as_ref()) {
+                None => {
+                    seen.insert(name.clone().into_owned(), key.span());
+                    false
+                }
+                Some(&first) if self.options.duplicate_keys == DuplicateKeys::Error => {
+                    return Err(ParseError::DuplicateKey {
+                        span: key.span(),
+                        first,
+                        name: name.into_owned(),
+                    });
+                }
+                Some(_) => true,
+            },
+        };
+
+        // A skipped member is still parsed, but nothing is built from it.
+        self.muted += usize::from(skip);
+        let result = self.member_rest(key, &name);
+        self.muted -= usize::from(skip);
+        result
+    }
+
+    ///
+    /// The rest of a member, from its key onwards.
+    ///
+    fn member_rest(&mut self, key: Key, name: &str) -> Result<(), ParseError> {
+        self.emit(|builder| builder.key(key, name));
+
+        self.lookahead()?;
+        let Some(&Token::Punctuator(Punct::Colon(colon))) = self.peek() else {
+            return Err(self.error("expected `:`"));
+        };
+        self.bump();
+        self.emit(|builder| builder.colon(colon));
+
+        self.value()?;
+        self.emit(B::end_member);
+        Ok(())
+    }
+
+    fn array(&mut self) -> Result<(), ParseError> {
+        let Token::Punctuator(Punct::OpenBracket(open)) = self.bump() else {
+            unreachable!("arrays start at a peeked `[`");
+        };
+        self.emit(|builder| builder.begin_array(open));
+
+        self.list(|punct| matches!(punct, Punct::CloseBracket(_)), Self::value)?;
+
+        let Token::Punctuator(Punct::CloseBracket(close)) = self.bump() else {
+            unreachable!("`list` stops at a peeked `]`");
+        };
+        self.emit(|builder| builder.end_array(close));
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+//!
+//! Parsing a document straight to a [`Value`].
+//!
+
+use crate::{common::SourceFile, value::Value};
+
+use super::{ast::parse_ast_with, ParseError, ParseOptions};
+
+///
+/// Parse a document into a [`Value`], skipping whitespace and comments.
+///
+/// Objects keep their keys in the order they were written. If an object
+/// has the same key more than once, the last value wins, but it keeps
+/// the position of the first.
+///
+/// ```
+/// use avjason::{common::SourceFile, parse::parse, value::Value};
+///
+/// let src = SourceFile::dummy_file("[1, 'two', null]");
+/// assert_eq!(
+///     parse(&src).unwrap(),
+///     Value::Array(vec![Value::Number(1.0), Value::String("two".into()), Value::Null]),
+/// );
+/// ```
+///
+pub fn parse(src: &SourceFile) -> Result<Value, ParseError> {
+    parse_with(src, &ParseOptions::default())
+}
+
+///
+/// Parse a document into a [`Value`], as with [`parse`], using `options`.
+///
+/// See [`ParseOptions::duplicate_keys`] for other ways
+/// to handle an object with the same key more than once.
+///
+pub fn parse_with(src: &SourceFile, options: &ParseOptions) -> Result<Value, ParseError> {
+    parse_ast_with(src, options).map(|ast| ast.to_value())
+}
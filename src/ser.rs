@@ -0,0 +1,689 @@
+//!
+//! Serializing Rust types to JSON5 with [`serde`].
+//!
+//! ```
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct Config {
+//!     name: String,
+//!     #[serde(rename = "max depth")]
+//!     max_depth: u8,
+//!     tags: Vec<&'static str>,
+//! }
+//!
+//! let config = Config { name: "avjason".into(), max_depth: 8, tags: vec!["json5"] };
+//! assert_eq!(
+//!     avjason::ser::to_string(&config).unwrap(),
+//!     r#"{name:"avjason","max depth":8,tags:["json5"]}"#,
+//! );
+//! ```
+//!
+
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+};
+use core::{error, fmt};
+
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::lex::{escapes::escape, identifier::LIdentifier, strings::QuoteStyle};
+
+///
+/// Serialize `value` as a JSON5 document, with the default options.
+///
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    to_string_with(value, &SerializeOptions::default())
+}
+
+///
+/// Serialize `value` as a JSON5 document, using `options`.
+///
+/// ```
+/// use avjason::{lex::strings::QuoteStyle, ser::{to_string_with, SerializeOptions}};
+///
+/// let options = SerializeOptions { quote: QuoteStyle::Single };
+/// assert_eq!(to_string_with(&["it's"], &options).unwrap(), r"['it\'s']");
+/// ```
+///
+pub fn to_string_with<T: Serialize + ?Sized>(
+    value: &T,
+    options: &SerializeOptions,
+) -> Result<String, Error> {
+    let mut serializer = Serializer {
+        out: String::new(),
+        options,
+    };
+    value.serialize(&mut serializer)?;
+    Ok(serializer.out)
+}
+
+///
+/// Options for how values are written.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerializeOptions {
+    ///
+    /// The quotes to write strings, and keys which are not
+    /// [identifier names](LIdentifier::is_identifier_name), in.
+    ///
+    /// Defaults to double quotes, as in JSON.
+    ///
+    pub quote: QuoteStyle,
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        Self {
+            quote: QuoteStyle::Double,
+        }
+    }
+}
+
+///
+/// Something went wrong whilst serializing.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    message: String,
+}
+
+impl Error {
+    ///
+    /// A description of the problem.
+    ///
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self {
+            message: msg.to_string(),
+        }
+    }
+}
+
+///
+/// Writes values as compact JSON5, with no whitespace.
+///
+/// Enums are written as [the deserializer](crate::de) reads them:
+/// unit variants as strings, and others as an object with a single key.
+///
+struct Serializer<'a> {
+    out: String,
+    options: &'a SerializeOptions,
+}
+
+impl Serializer<'_> {
+    fn string(&mut self, text: &str) {
+        let quote = self.options.quote;
+        self.out.push(quote.as_char());
+        self.out.push_str(&escape(text, quote));
+        self.out.push(quote.as_char());
+    }
+
+    fn key(&mut self, key: &str) {
+        match LIdentifier::is_identifier_name(key) {
+            true => self.out.push_str(key),
+            false => self.string(key),
+        }
+    }
+
+    ///
+    /// Open the single-key object around a variant's value.
+    ///
+    fn variant(&mut self, variant: &str) {
+        self.out.push('{');
+        self.key(variant);
+        self.out.push(':');
+    }
+}
+
+impl<'a, 'o> ser::Serializer for &'a mut Serializer<'o> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Compound<'a, 'o>;
+    type SerializeTuple = Compound<'a, 'o>;
+    type SerializeTupleStruct = Compound<'a, 'o>;
+    type SerializeTupleVariant = Compound<'a, 'o>;
+    type SerializeMap = Compound<'a, 'o>;
+    type SerializeStruct = Compound<'a, 'o>;
+    type SerializeStructVariant = Compound<'a, 'o>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.out.push_str(if v { "true" } else { "false" });
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.out.push_str(&v.to_string());
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        self.out.push_str(&v.to_string());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.out.push_str(&v.to_string());
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.out.push_str(&v.to_string());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        match v.is_finite() {
+            // Written as an `f32`, so `0.1` is not widened to `0.10000000149011612`.
+            true => self.out.push_str(&format!("{v:?}")),
+            false => return self.serialize_f64(v.into()),
+        }
+        Ok(())
+    }
+
+    ///
+    /// JSON5, unlike JSON, can write `Infinity` and `NaN`.
+    ///
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        match v {
+            f64::INFINITY => self.out.push_str("Infinity"),
+            f64::NEG_INFINITY => self.out.push_str("-Infinity"),
+            _ if v.is_nan() => self.out.push_str("NaN"),
+            // `Debug` keeps the decimal point, and uses an exponent for
+            // very large or small numbers, both of which JSON5 can read.
+            _ => self.out.push_str(&format!("{v:?}")),
+        }
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.string(v.encode_utf8(&mut [0; 4]));
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.string(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        v.serialize(self)
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.out.push_str("null");
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.variant(variant);
+        value.serialize(&mut *self)?;
+        self.out.push('}');
+        Ok(())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a, 'o>, Error> {
+        Ok(Compound::open(self, '[', ']', false))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'a, 'o>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a, 'o>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, 'o>, Error> {
+        self.variant(variant);
+        Ok(Compound::open(self, '[', ']', true))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a, 'o>, Error> {
+        Ok(Compound::open(self, '{', '}', false))
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound<'a, 'o>, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, 'o>, Error> {
+        self.variant(variant);
+        Ok(Compound::open(self, '{', '}', true))
+    }
+}
+
+///
+/// An array or object, part way through being written.
+///
+struct Compound<'a, 'o> {
+    ser: &'a mut Serializer<'o>,
+    close: char,
+    // Whether this is the value of a variant, which needs another `}`.
+    variant: bool,
+    first: bool,
+}
+
+impl<'a, 'o> Compound<'a, 'o> {
+    fn open(ser: &'a mut Serializer<'o>, open: char, close: char, variant: bool) -> Self {
+        ser.out.push(open);
+        Self {
+            ser,
+            close,
+            variant,
+            first: true,
+        }
+    }
+
+    fn comma(&mut self) {
+        if !core::mem::take(&mut self.first) {
+            self.ser.out.push(',');
+        }
+    }
+
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.comma();
+        value.serialize(&mut *self.ser)
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+        self.comma();
+        self.ser.key(key);
+        self.ser.out.push(':');
+        value.serialize(&mut *self.ser)
+    }
+
+    fn close(self) -> Result<(), Error> {
+        self.ser.out.push(self.close);
+        if self.variant {
+            self.ser.out.push('}');
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.comma();
+        let key = key.serialize(KeySerializer)?;
+        self.ser.key(&key);
+        self.ser.out.push(':');
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close()
+    }
+}
+
+///
+/// Turns a map key into the text of an object key.
+///
+/// Object keys are always strings, so only strings, characters,
+/// unit variants and integers (written in decimal) can be keys.
+///
+struct KeySerializer;
+
+impl KeySerializer {
+    fn integer(v: impl ToString) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn invalid() -> Result<String, Error> {
+        Err(ser::Error::custom(
+            "object keys must be strings, characters, unit variants or integers",
+        ))
+    }
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = Error;
+
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    fn serialize_str(self, v: &str) -> Result<String, Error> {
+        Ok(v.to_owned())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<String, Error> {
+        Ok(variant.to_owned())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, Error> {
+        Self::integer(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, Error> {
+        Self::integer(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, Error> {
+        Self::integer(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, Error> {
+        Self::integer(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<String, Error> {
+        Self::integer(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, Error> {
+        Self::integer(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, Error> {
+        Self::integer(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, Error> {
+        Self::integer(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, Error> {
+        Self::integer(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<String, Error> {
+        Self::integer(v)
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<String, Error> {
+        Self::invalid()
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<String, Error> {
+        Self::invalid()
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<String, Error> {
+        Self::invalid()
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, Error> {
+        Self::invalid()
+    }
+
+    fn serialize_none(self) -> Result<String, Error> {
+        Self::invalid()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<String, Error> {
+        Self::invalid()
+    }
+
+    fn serialize_unit(self) -> Result<String, Error> {
+        Self::invalid()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, Error> {
+        Self::invalid()
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, Error> {
+        Self::invalid()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(ser::Error::custom("object keys cannot be arrays"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(ser::Error::custom("object keys cannot be arrays"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(ser::Error::custom("object keys cannot be arrays"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(ser::Error::custom("object keys cannot be arrays"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(ser::Error::custom("object keys cannot be objects"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(ser::Error::custom("object keys cannot be objects"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(ser::Error::custom("object keys cannot be objects"))
+    }
+}
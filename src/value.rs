@@ -0,0 +1,240 @@
+//!
+//! Owned representation of a JSON5 value.
+//!
+
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::ops::Index;
+
+pub use indexmap::IndexMap;
+
+use crate::collections::RandomState;
+
+///
+/// The members of a [`Value::Object`].
+///
+/// With the `std` feature, this is just `IndexMap<String, Value>`.
+///
+pub type Map = IndexMap<String, Value, RandomState>;
+
+///
+/// A JSON5 value.
+///
+/// Objects keep their keys in the order they were written.
+/// Comparing two objects ignores that order, though.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Map),
+}
+
+// What indexing returns for a missing key or index.
+static NULL: Value = Value::Null;
+
+impl Value {
+    ///
+    /// Is this `null`?
+    ///
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    ///
+    /// The boolean, if this is `true` or `false`.
+    ///
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    ///
+    /// The number, if this is one. This includes `Infinity` and `NaN`.
+    ///
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    ///
+    /// The string, if this is one, with any escapes already decoded.
+    ///
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    ///
+    /// The elements, if this is an array.
+    ///
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Self::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    ///
+    /// The members, if this is an object, in the order they were written.
+    ///
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            Self::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Extract a homogeneous array of scalars.
+    ///
+    /// Returns `None` if this value is not an array,
+    /// or if any element is not a `T`.
+    ///
+    /// ```
+    /// use avjason::value::Value;
+    ///
+    /// let v = Value::Array(vec![Value::Bool(true), Value::Bool(false)]);
+    /// assert_eq!(v.as_typed_array::<bool>(), Some(vec![true, false]));
+    /// ```
+    ///
+    pub fn as_typed_array<T: FromValue>(&self) -> Option<Vec<T>> {
+        match self {
+            Value::Array(items) => items.iter().map(T::from_value).collect(),
+            _ => None,
+        }
+    }
+
+    ///
+    /// The member named `key`, if this is an object which has one.
+    ///
+    /// ```
+    /// use avjason::{common::SourceFile, parse::parse};
+    ///
+    /// let value = parse(&SourceFile::dummy_file("{ a: { b: [1, 2] } }")).unwrap();
+    /// assert_eq!(value.get("a").and_then(|a| a.get("b")), Some(&value["a"]["b"]));
+    /// assert_eq!(value.get("b"), None);
+    /// ```
+    ///
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object()?.get(key)
+    }
+
+    ///
+    /// Follow a [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901),
+    /// such as `/a/0/b`, from this value.
+    ///
+    /// Each `/`-separated segment is a key, or an index into an array.
+    /// Within a key, `~1` stands for `/` and `~0` for `~`. The empty
+    /// pointer is this value itself.
+    ///
+    /// Returns `None` if the pointer does not start with `/`,
+    /// or any segment is missing.
+    ///
+    /// ```
+    /// use avjason::{common::SourceFile, parse::parse, value::Value};
+    ///
+    /// let value = parse(&SourceFile::dummy_file("{ a: [{ 'b/c': 1 }] }")).unwrap();
+    /// assert_eq!(value.pointer("/a/0/b~1c"), Some(&Value::Number(1.0)));
+    /// assert_eq!(value.pointer("/a/1"), None);
+    /// ```
+    ///
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+
+        pointer
+            .strip_prefix('/')?
+            .split('/')
+            .try_fold(self, |value, segment| match value {
+                Self::Object(members) => {
+                    members.get(segment.replace("~1", "/").replace("~0", "~").as_str())
+                }
+                Self::Array(items) => items.get(array_index(segment)?),
+                _ => None,
+            })
+    }
+}
+
+///
+/// Read a JSON pointer segment as an array index.
+///
+/// Indices are written in decimal, without leading zeros.
+///
+fn array_index(segment: &str) -> Option<usize> {
+    let digits = !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit());
+    if !digits || (segment.len() > 1 && segment.starts_with('0')) {
+        return None;
+    }
+
+    segment.parse().ok()
+}
+
+///
+/// Index into an object by key.
+///
+/// Like `serde_json`, this gives [`Value::Null`] rather than panicking
+/// if this is not an object, or it has no such key,
+/// so lookups can be chained: `value["a"]["b"]`.
+///
+impl Index<&str> for Value {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Value {
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+///
+/// Index into an array by position.
+///
+/// This gives [`Value::Null`] rather than panicking if this is not
+/// an array, or the index is out of bounds.
+///
+impl Index<usize> for Value {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Value {
+        self.as_array()
+            .and_then(|items| items.get(index))
+            .unwrap_or(&NULL)
+    }
+}
+
+///
+/// Scalar types which can be extracted from a [`Value`].
+///
+pub trait FromValue: Sized {
+    ///
+    /// Extract `Self` from `value`, returning `None`
+    /// if the value is of the wrong type.
+    ///
+    fn from_value(value: &Value) -> Option<Self>;
+}
+
+impl FromValue for f64 {
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_f64()
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_str().map(str::to_owned)
+    }
+}
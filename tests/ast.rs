@@ -0,0 +1,99 @@
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    parse::{
+        ast::{Key, Literal, Value},
+        parse_ast,
+    },
+};
+
+///
+/// Parse `source` into an abstract syntax tree.
+///
+fn ast(source: &str) -> Value {
+    parse_ast(&SourceFile::dummy_file(source)).unwrap()
+}
+
+#[test]
+fn array_span() {
+    let Value::Array(array) = ast("  [1, 'two' , [] ] // done") else {
+        panic!("expected an array");
+    };
+
+    assert_eq!(array.span(), Span::new(2, 18));
+    assert_eq!(array.open.span(), Span::new(2, 3));
+    assert_eq!(array.close.span(), Span::new(17, 18));
+
+    let spans: Vec<_> = array.elements.iter().map(Spanned::span).collect();
+    assert_eq!(
+        spans,
+        [Span::new(3, 4), Span::new(6, 11), Span::new(14, 16)]
+    );
+}
+
+#[test]
+fn member_spans() {
+    let source = "{ a: 1, 'b' : null }";
+    let Value::Object(object) = ast(source) else {
+        panic!("expected an object");
+    };
+
+    assert_eq!(object.span(), Span::new(0, 20));
+
+    let [a, b] = object.members.as_slice() else {
+        panic!("expected two members");
+    };
+
+    assert!(matches!(a.key, Key::Identifier(_)));
+    assert_eq!(a.name, "a");
+    assert_eq!(a.key.span(), Span::new(2, 3));
+    assert_eq!(a.colon.span(), Span::new(3, 4));
+    assert_eq!(a.value.span(), Span::new(5, 6));
+    assert_eq!(a.span(), Span::new(2, 6));
+
+    assert!(matches!(b.key, Key::String(_)));
+    assert_eq!(b.name, "b");
+    assert_eq!(b.colon.span(), Span::new(12, 13));
+    assert_eq!(b.span(), Span::new(8, 18));
+}
+
+#[test]
+fn literals() {
+    let Value::Array(array) = ast(r"[null, true, false, 'a\nb', -0x10]") else {
+        panic!("expected an array");
+    };
+
+    let literals: Vec<_> = array
+        .elements
+        .iter()
+        .map(|element| match element {
+            Value::Literal(literal) => literal,
+            _ => panic!("expected a literal"),
+        })
+        .collect();
+
+    assert!(matches!(literals[0], Literal::Null(_)));
+    assert!(matches!(literals[1], Literal::Bool { value: true, .. }));
+    assert!(matches!(literals[2], Literal::Bool { value: false, .. }));
+    assert!(matches!(literals[3], Literal::String { value, .. } if value == "a\nb"));
+    assert!(matches!(literals[4], Literal::Number { value, .. } if *value == -16.0));
+    assert_eq!(literals[4].span(), Span::new(28, 33));
+}
+
+#[test]
+fn lookup_by_decoded_key() {
+    let source = r"{ a: 1, 'b': 2, \u0063: 3, a: 4 }";
+    let Value::Object(object) = ast(source) else {
+        panic!("expected an object");
+    };
+
+    let src = SourceFile::dummy_file(source);
+    let value = |name| object.get(name).map(|value| value.span().raw(&src));
+    assert_eq!(value("a"), Some("4"));
+    assert_eq!(value("b"), Some("2"));
+    assert_eq!(value("c"), Some("3"));
+    assert_eq!(value("d"), None);
+
+    // The key keeps the token as written.
+    assert_eq!(object.members[2].key.raw(&src), r"\u0063");
+    assert_eq!(object.members[2].name, "c");
+}
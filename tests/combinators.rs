@@ -0,0 +1,154 @@
+mod common;
+
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    lex::{
+        combinators::{AtLeast, Exactly, Many, Optional, Separated},
+        digits::HexDigit,
+        tokens::Comma,
+        Lex, LexError,
+    },
+    Token,
+};
+
+use self::common::alloc::counting;
+
+///
+/// Lex a `T` from the start of `source`.
+///
+fn lex<T: Lex>(source: &str) -> Result<T, LexError> {
+    let src = SourceFile::dummy_file(source);
+    T::lex(&mut src.iter())
+}
+
+#[test]
+fn exactly() {
+    let digits = lex::<Exactly<4, HexDigit>>("beef!").unwrap();
+    assert_eq!(digits.span(), Span::new(0, 4));
+    assert_eq!(digits.len(), 4);
+
+    let values: Vec<_> = digits.iter().map(HexDigit::value).collect();
+    assert_eq!(values, [0xb, 0xe, 0xe, 0xf]);
+    assert_eq!(digits.as_array()[3].span(), Span::new(3, 4));
+
+    assert!(lex::<Exactly<4, HexDigit>>("bee!").is_err());
+    assert!(lex::<Exactly<4, HexDigit>>("").is_err());
+}
+
+#[test]
+fn exactly_does_not_allocate() {
+    let src = SourceFile::dummy_file("0041");
+
+    let (digits, counts) = counting(|| Exactly::<4, HexDigit>::lex(&mut src.iter()).unwrap());
+    assert_eq!(counts.allocations, 0, "lexing {digits:?} allocated");
+}
+
+#[test]
+fn many() {
+    let digits = lex::<Many<HexDigit>>("deadBEEF").unwrap();
+    assert_eq!(digits.span(), Span::new(0, 8));
+
+    let values: Vec<_> = digits.iter().map(HexDigit::value).collect();
+    assert_eq!(values, [0xd, 0xe, 0xa, 0xd, 0xb, 0xe, 0xe, 0xf]);
+
+    let src = SourceFile::dummy_file("be!");
+    let mut input = src.iter();
+    assert_eq!(Many::<HexDigit>::lex(&mut input).unwrap().len(), 2);
+    assert_eq!(input.offset(), 2);
+
+    let none = lex::<Many<HexDigit>>("").unwrap();
+    assert!(none.is_empty());
+    assert_eq!(none.span(), Span::empty(0));
+}
+
+#[test]
+fn at_least() {
+    let one = lex::<AtLeast<1, HexDigit>>("a").unwrap();
+    assert_eq!(one.len(), 1);
+    assert_eq!(one.span(), Span::new(0, 1));
+
+    let more = lex::<AtLeast<1, HexDigit>>("abc!").unwrap();
+    assert_eq!(more.len(), 3);
+    assert_eq!(more.span(), Span::new(0, 3));
+
+    assert!(lex::<AtLeast<1, HexDigit>>("").is_err());
+    assert!(lex::<AtLeast<1, HexDigit>>("!").is_err());
+    assert!(lex::<AtLeast<3, HexDigit>>("ab!").is_err());
+}
+
+#[test]
+fn separated() {
+    let src = SourceFile::dummy_file("a,b,c,");
+    let mut input = src.iter();
+    let list = Separated::<HexDigit, Comma>::lex(&mut input).unwrap();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.separators().len(), 2);
+    assert_eq!(list.trailing(), None);
+    assert_eq!(list.span(), Span::new(0, 5));
+    // The trailing comma is left for whatever comes next.
+    assert_eq!(input.offset(), 5);
+
+    let mut input = src.iter();
+    let list = Separated::<HexDigit, Comma, true>::lex(&mut input).unwrap();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.separators().len(), 3);
+    assert_eq!(list.trailing().map(Spanned::span), Some(Span::new(5, 6)));
+    assert_eq!(list.span(), Span::new(0, 6));
+    assert_eq!(input.offset(), 6);
+
+    let one = lex::<Separated<HexDigit, Comma, true>>("a").unwrap();
+    assert_eq!(one.len(), 1);
+    assert_eq!(one.trailing(), None);
+
+    assert!(lex::<Separated<HexDigit, Comma, true>>(",a").is_err());
+    assert!(lex::<Separated<HexDigit, Comma, true>>("").is_err());
+}
+
+#[test]
+fn optional() {
+    let src = SourceFile::dummy_file("+1");
+    let mut input = src.iter();
+    let plus = Optional::<Token![+]>::lex(&mut input).unwrap();
+    assert_eq!(plus.as_ref().map(Spanned::span), Some(Span::new(0, 1)));
+    assert_eq!(plus.span(), Span::new(0, 1));
+    assert_eq!(input.offset(), 1);
+
+    // Nothing is consumed, and the empty span is where the `+` would be.
+    let absent = Optional::<Token![+]>::lex(&mut input).unwrap();
+    assert!(absent.as_ref().is_none());
+    assert_eq!(absent.span(), Span::empty(1));
+    assert_eq!(input.offset(), 1);
+
+    let empty = lex::<Optional<Token![+]>>("").unwrap();
+    assert_eq!(empty.into_inner(), None);
+}
+
+#[test]
+fn empty_repetitions() {
+    let src = SourceFile::dummy_file("ab!");
+    let mut input = src.iter();
+    input.next();
+    input.next();
+
+    // None of these consume anything, and their spans are empty
+    // where the tokens would have been.
+    assert!(Exactly::<0, HexDigit>::peek(&input));
+    let none = Exactly::<0, HexDigit>::lex(&mut input).unwrap();
+    assert!(none.is_empty());
+    assert_eq!(none.span(), Span::empty(2));
+    assert_eq!(none.as_array().span(), Span::empty(0));
+
+    let none = Many::<HexDigit>::lex(&mut input).unwrap();
+    assert!(none.is_empty());
+    assert_eq!(none.span(), Span::empty(2));
+
+    let none = AtLeast::<0, HexDigit>::lex(&mut input).unwrap();
+    assert!(none.is_empty());
+    assert_eq!(none.span(), Span::empty(2));
+    assert_eq!(input.offset(), 2);
+
+    let none = lex::<Exactly<0, HexDigit>>("").unwrap();
+    assert_eq!(none.span(), Span::empty(0));
+    let none = lex::<Many<HexDigit>>("").unwrap();
+    assert_eq!(none.span(), Span::empty(0));
+}
@@ -0,0 +1,65 @@
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    lex::{comment::Comment, InputElement, Lex, LexError, LexOptions},
+};
+
+fn lex(source: &str, options: LexOptions) -> Result<Comment, LexError> {
+    let src = SourceFile::dummy_file(source);
+    Comment::lex(&mut src.iter_with(options))
+}
+
+#[test]
+fn comments_do_not_nest_by_default() {
+    let source = "/* a /* b */ c */";
+    let comment = lex(source, LexOptions::default()).unwrap();
+    assert_eq!(comment.span(), Span::new(0, 12));
+}
+
+#[test]
+fn nested_comments() {
+    let options = LexOptions {
+        nested_comments: true,
+    };
+
+    let source = "/* a /* b */ c */";
+    let comment = lex(source, options).unwrap();
+    assert_eq!(comment.span(), Span::new(0, source.len()));
+
+    let source = "/*/**/*/";
+    assert_eq!(lex(source, options).unwrap().span(), Span::new(0, 8));
+
+    let error = lex("/* a /* b */ c", options).unwrap_err();
+    assert_eq!(error.message(), "unterminated block comment");
+    assert_eq!(error.span(), Span::new(0, 14));
+}
+
+#[test]
+fn comment_text() {
+    let text = |source: &str| {
+        let src = SourceFile::dummy_file(source);
+        let comment = Comment::lex(&mut src.iter()).unwrap();
+        comment.text(&src).to_string()
+    };
+
+    assert_eq!(text("// hello"), " hello");
+    assert_eq!(text("// hello\nworld"), " hello");
+    assert_eq!(text("// hello\r\n"), " hello");
+    assert_eq!(text("//"), "");
+
+    assert_eq!(text("/* x */"), " x ");
+    assert_eq!(text("/* 💩\n é */"), " 💩\n é ");
+    assert_eq!(text("/**/"), "");
+}
+
+#[test]
+fn unterminated_block_comment() {
+    for source in ["/* never closed", "/* almost *", "/*"] {
+        let error = lex(source, LexOptions::default()).unwrap_err();
+        assert_eq!(error.message(), "unterminated block comment");
+        assert_eq!(error.span(), Span::new(0, source.len()));
+    }
+
+    // The error is reported by the general lexer too.
+    let src = SourceFile::dummy_file("/* never closed");
+    assert!(InputElement::lex(&mut src.iter()).is_err());
+}
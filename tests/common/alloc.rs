@@ -0,0 +1,99 @@
+//!
+//! A global allocator which counts what each thread allocates,
+//! so tests running in parallel don't see each other's.
+//!
+
+// Each test only looks at some of the counts.
+#![allow(dead_code)]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+///
+/// What has been allocated on one thread.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counts {
+    ///
+    /// Calls to `alloc`.
+    ///
+    pub allocations: usize,
+
+    ///
+    /// Calls to `realloc`, as when a `Vec` grows.
+    ///
+    pub reallocations: usize,
+
+    ///
+    /// Bytes allocated less bytes freed. This can be negative,
+    /// as a thread may free what another allocated.
+    ///
+    pub live: isize,
+}
+
+thread_local! {
+    static COUNTS: Cell<Counts> = const {
+        Cell::new(Counts {
+            allocations: 0,
+            reallocations: 0,
+            live: 0,
+        })
+    };
+}
+
+///
+/// Update this thread's counts, unless it is being torn down.
+///
+fn record(update: impl FnOnce(&mut Counts)) {
+    let _ = COUNTS.try_with(|counts| {
+        let mut now = counts.get();
+        update(&mut now);
+        counts.set(now);
+    });
+}
+
+struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(|counts| {
+            counts.allocations += 1;
+            counts.live += layout.size() as isize;
+        });
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(|counts| counts.live -= layout.size() as isize);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(|counts| {
+            counts.reallocations += 1;
+            counts.live += new_size as isize - layout.size() as isize;
+        });
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+///
+/// Run `f`, counting what it allocates on this thread.
+///
+pub fn counting<T>(f: impl FnOnce() -> T) -> (T, Counts) {
+    let before = COUNTS.with(Cell::get);
+    let result = f();
+    let after = COUNTS.with(Cell::get);
+
+    let counts = Counts {
+        allocations: after.allocations - before.allocations,
+        reallocations: after.reallocations - before.reallocations,
+        live: after.live - before.live,
+    };
+    (result, counts)
+}
@@ -0,0 +1,8 @@
+//!
+//! Helpers shared between the integration tests.
+//!
+//! Each test file which needs these declares `mod common;`,
+//! so a file here is not a test of its own.
+//!
+
+pub mod alloc;
@@ -0,0 +1,134 @@
+use avjason::{
+    common::{SourceFile, Span},
+    parse::{parse, parse_cst, parse_cst_with, ParseError, ParseOptions},
+};
+
+const DOCUMENT: &str = "// Configuration for something.\r\n\
+{\n\
+  /* A block\n     comment. */\n\
+  name: 'avjason', // trailing comment\n\
+  \"list\" : [1, 2.5,\n\
+\t\t3,  ],\n\
+\n\
+  nested: {inner: [ ], other: {}},\n\
+}\n\
+\n";
+
+#[test]
+fn round_trip() {
+    let src = SourceFile::dummy_file(DOCUMENT);
+    let cst = parse_cst(&src).unwrap();
+    assert_eq!(cst.to_string(), DOCUMENT);
+}
+
+#[test]
+fn round_trip_with_bom() {
+    let source = format!("\u{FEFF}{DOCUMENT}");
+    let src = SourceFile::dummy_file(source.as_str());
+    let cst = parse_cst(&src).unwrap();
+    assert_eq!(cst.to_string(), source);
+}
+
+#[test]
+fn edits_keep_trivia() {
+    let src = SourceFile::dummy_file(DOCUMENT);
+    let mut cst = parse_cst(&src).unwrap();
+
+    let root = cst.value_mut();
+    root.get_mut("name")
+        .unwrap()
+        .replace_with("\"json5\"")
+        .unwrap();
+    root.get_mut("list")
+        .unwrap()
+        .index_mut(2)
+        .unwrap()
+        .replace_with("{ three: 3 }")
+        .unwrap();
+
+    let expected = DOCUMENT
+        .replace("'avjason'", "\"json5\"")
+        .replace("\t\t3,", "\t\t{ three: 3 },");
+    assert_eq!(cst.to_string(), expected);
+}
+
+#[test]
+fn replacements_skip_trivia() {
+    let src = SourceFile::dummy_file("[1, /* two */ 2]");
+    let mut cst = parse_cst(&src).unwrap();
+
+    let root = cst.value_mut();
+    root.index_mut(0).unwrap().replace_with(" 3").unwrap();
+    root.index_mut(1)
+        .unwrap()
+        .replace_with("// four\n4 /* ! */\n")
+        .unwrap();
+    assert_eq!(cst.to_string(), "[3, /* two */ 4]");
+
+    let root = cst.value_mut();
+    assert!(root.index_mut(0).unwrap().replace_with(" ").is_err());
+    assert!(root.index_mut(0).unwrap().replace_with(" 1 2").is_err());
+}
+
+#[test]
+fn rejects_malformed_documents() {
+    for source in [
+        "", "{", "[1 2]", "{a 1}", "{1: 2}", "[1] [2]", "[,]", "[yes]",
+    ] {
+        let src = SourceFile::dummy_file(source);
+        assert!(parse_cst(&src).is_err(), "{source:?} should not parse");
+    }
+}
+
+#[test]
+fn max_depth() {
+    let src = SourceFile::dummy_file("[ /* one */ [ /* two */ [] ] ]");
+    assert!(parse_cst(&src).is_ok());
+
+    let options = ParseOptions {
+        max_depth: 2,
+        ..ParseOptions::default()
+    };
+    assert_eq!(
+        parse_cst_with(&src, &options).unwrap_err(),
+        ParseError::DepthExceeded {
+            span: Span::new(24, 25)
+        }
+    );
+}
+
+#[test]
+fn keys_are_decoded() {
+    let src = SourceFile::dummy_file(r#"{ "\u0061": 1, b\u0063: 2, 'd': 3 }"#);
+    let mut cst = parse_cst(&src).unwrap();
+
+    let root = cst.value_mut();
+    root.get_mut("a").unwrap().replace_with("4").unwrap();
+    root.get_mut("bc").unwrap().replace_with("5").unwrap();
+    root.get_mut("d").unwrap().replace_with("6").unwrap();
+    assert!(root.get_mut("\\u0061").is_none());
+    assert_eq!(cst.to_string(), r#"{ "\u0061": 4, b\u0063: 5, 'd': 6 }"#);
+}
+
+#[test]
+fn same_errors_as_the_tree() {
+    for source in [
+        "[1e400]",
+        "['\\x4']",
+        "{ '\\u00': 1 }",
+        "0x",
+        "[,]",
+        "[1 2]",
+        "{ a 1 }",
+        "{ 1: 2 }",
+        "[1] // done\n2",
+    ] {
+        let src = SourceFile::dummy_file(source);
+        assert_eq!(
+            parse_cst(&src).map(drop),
+            parse(&src).map(drop),
+            "{source:?}"
+        );
+        assert!(parse_cst(&src).is_err(), "{source:?} should not parse");
+    }
+}
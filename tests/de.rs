@@ -0,0 +1,175 @@
+#![cfg(feature = "serde")]
+
+use std::{borrow::Cow, collections::BTreeMap};
+
+use avjason::{common::Span, de::from_str};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    name: String,
+    version: u32,
+    ratio: f64,
+    debug: bool,
+    tags: Vec<String>,
+    parent: Option<Box<Config>>,
+    limits: Option<Limits>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Limits {
+    depth: u8,
+    offsets: Vec<i64>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Color {
+    Red,
+    Rgb(u8, u8, u8),
+    Named { name: String },
+}
+
+#[test]
+fn nested_struct() {
+    let config: Config = from_str(
+        "{
+            name: 'child',
+            version: 2,
+            ratio: .5,
+            debug: false,
+            tags: ['a', \"b\",],
+            // The parent has no parent.
+            parent: {
+                name: 'root', version: 0x1, ratio: 1, debug: true,
+                tags: [], parent: null,
+            },
+            limits: { depth: 8, offsets: [-1, +2] },
+        }",
+    )
+    .unwrap();
+
+    assert_eq!(
+        config,
+        Config {
+            name: "child".into(),
+            version: 2,
+            ratio: 0.5,
+            debug: false,
+            tags: vec!["a".into(), "b".into()],
+            parent: Some(Box::new(Config {
+                name: "root".into(),
+                version: 1,
+                ratio: 1.0,
+                debug: true,
+                tags: vec![],
+                parent: None,
+                limits: None,
+            })),
+            limits: Some(Limits {
+                depth: 8,
+                offsets: vec![-1, 2],
+            }),
+        }
+    );
+}
+
+#[test]
+fn scalars_and_maps() {
+    assert_eq!(from_str::<char>("'x'"), Ok('x'));
+    assert_eq!(from_str::<i8>("-0x80"), Ok(i8::MIN));
+    assert!(from_str::<f64>("NaN").unwrap().is_nan());
+    assert!(from_str::<f64>("-0").unwrap().is_sign_negative());
+    assert!(from_str::<f32>("-0.0").unwrap().is_sign_negative());
+    assert_eq!(from_str::<i32>("-0"), Ok(0));
+    assert_eq!(from_str::<u8>("-0x0"), Ok(0));
+    assert_eq!(from_str::<()>("null"), Ok(()));
+    assert_eq!(
+        from_str::<(u8, String)>("[1, 'one']"),
+        Ok((1, "one".into()))
+    );
+    assert_eq!(
+        from_str::<BTreeMap<String, bool>>("{ yes: true, 'no': false }"),
+        Ok(BTreeMap::from([("yes".into(), true), ("no".into(), false)]))
+    );
+    assert_eq!(
+        from_str::<BTreeMap<i8, ()>>("{ '-1': null, '2': null }"),
+        Ok(BTreeMap::from([(-1, ()), (2, ())]))
+    );
+}
+
+#[test]
+fn enums() {
+    assert_eq!(from_str::<Color>("'Red'"), Ok(Color::Red));
+    assert_eq!(
+        from_str::<Color>("{ Rgb: [1, 2, 3] }"),
+        Ok(Color::Rgb(1, 2, 3))
+    );
+    assert_eq!(
+        from_str::<Color>("{ Named: { name: 'teal' } }"),
+        Ok(Color::Named {
+            name: "teal".into()
+        })
+    );
+
+    let error = from_str::<Color>("{ Red: null, Rgb: [] }").unwrap_err();
+    assert_eq!(error.span(), Some(Span::new(0, 22)));
+}
+
+#[test]
+fn error_spans() {
+    // A syntax error.
+    let error = from_str::<Vec<u8>>("[1, 2").unwrap_err();
+    assert_eq!(error.span(), Some(Span::empty(5)));
+
+    // The wrong type, deep inside the document.
+    let error = from_str::<Limits>("{ depth: 1, offsets: [1, 'two'] }").unwrap_err();
+    assert_eq!(error.span(), Some(Span::new(25, 30)));
+    assert_eq!(
+        error.to_string(),
+        "invalid type: string \"two\", expected i64 at 25..30"
+    );
+
+    // Out of range.
+    let error = from_str::<Limits>("{ depth: 256, offsets: [] }").unwrap_err();
+    assert_eq!(error.span(), Some(Span::new(9, 12)));
+
+    // A missing field is blamed on the whole object.
+    let error = from_str::<Limits>("{ depth: 1 }").unwrap_err();
+    assert_eq!(error.message(), "missing field `offsets`");
+    assert_eq!(error.span(), Some(Span::new(0, 12)));
+}
+
+#[derive(Debug, Deserialize)]
+struct Borrowed<'a> {
+    #[serde(borrow)]
+    plain: Cow<'a, str>,
+    #[serde(borrow)]
+    escaped: Cow<'a, str>,
+    name: &'a str,
+}
+
+#[test]
+fn borrowed_strings() {
+    let input = String::from(r"{ plain: 'no escapes', escaped: 'tab\there', name: 'ok' }");
+    let borrowed: Borrowed = from_str(&input).unwrap();
+
+    assert!(matches!(borrowed.plain, Cow::Borrowed("no escapes")));
+    assert!(matches!(borrowed.escaped, Cow::Owned(ref s) if s == "tab\there"));
+    assert_eq!(borrowed.name, "ok");
+    assert!(input
+        .as_bytes()
+        .as_ptr_range()
+        .contains(&borrowed.name.as_ptr()));
+
+    // An escaped string has no unescaped text in the input to borrow.
+    let error = from_str::<Borrowed>(r"{ plain: '', escaped: '', name: 'n\x61me' }").unwrap_err();
+    assert_eq!(
+        error.message(),
+        "invalid type: string \"name\", expected a borrowed string"
+    );
+
+    // Keys borrow too, unless they are escaped.
+    let map: BTreeMap<&str, Cow<str>> = from_str("{ 'é': 'ü', b: \"\" }").unwrap();
+    assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["b", "é"]);
+    assert!(from_str::<BTreeMap<&str, ()>>(r"{ '\x62': null }").is_err());
+}
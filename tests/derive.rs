@@ -0,0 +1,345 @@
+#![allow(dead_code)]
+
+use std::marker::PhantomData;
+
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    lex::Lex,
+};
+
+#[derive(Spanned)]
+struct Marked {
+    other: Span,
+    #[span]
+    range: Span,
+}
+
+#[derive(Spanned)]
+struct Named {
+    inner: Span,
+    span: Span,
+}
+
+#[derive(Spanned)]
+struct Typed {
+    name: &'static str,
+    at: Span,
+    to: Option<Span>,
+}
+
+#[derive(Spanned)]
+struct Merged(Option<Span>, Vec<Span>);
+
+#[derive(Spanned)]
+struct Indexed(&'static str, Vec<Span>, Span);
+
+#[derive(Spanned)]
+enum Tuples {
+    Typed(char, Span),
+    Merged(Option<Span>, Vec<Span>),
+}
+
+#[derive(Spanned)]
+struct Skipped {
+    open: Option<Span>,
+    #[spanned(skip)]
+    marker: PhantomData<u8>,
+    #[spanned(skip)]
+    cached: String,
+    close: Option<Span>,
+}
+
+#[derive(Spanned)]
+enum Either {
+    Left(Named),
+    Right {
+        #[span]
+        outer: Span,
+        inner: Span,
+    },
+    Both(Span, #[span] Typed),
+}
+
+#[derive(Spanned)]
+enum Marker {
+    Value(Span),
+    #[spanned(empty)]
+    Missing,
+    #[spanned(empty)]
+    Nothing {},
+}
+
+#[derive(Spanned)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+#[derive(Spanned)]
+struct Pair<T, U>(Option<T>, Vec<U>);
+
+#[derive(Spanned)]
+struct Tagged<T, M> {
+    value: Box<T>,
+    #[spanned(skip)]
+    marker: PhantomData<M>,
+}
+
+#[derive(Debug, Spanned, Lex)]
+#[lex("=>")]
+struct Arrow {
+    span: Span,
+}
+
+#[derive(Debug, Spanned, Lex)]
+#[lex('@')]
+struct At(Span);
+
+#[derive(Debug, Spanned, Lex)]
+#[lex('=')]
+struct Equals(Span);
+
+#[derive(Debug, Spanned, Lex)]
+#[lex("Infinity")]
+struct Infinity(Span);
+
+#[derive(Debug, Spanned, Lex)]
+#[lex("∞→")]
+struct Arrows(Span);
+
+#[derive(Debug, Spanned, Lex)]
+#[lex("null", keyword)]
+struct NullKeyword(Span);
+
+#[derive(Debug, Spanned, Lex)]
+#[lex("nan", ignore_case)]
+struct AnyCaseNaN(Span);
+
+#[derive(Debug, Spanned, Lex)]
+enum Declared {
+    Equals(Equals),
+    Arrow(Arrow),
+}
+
+#[derive(Debug, Spanned, Lex)]
+enum Prioritised {
+    Equals(Equals),
+    #[lex(priority = 1)]
+    Arrow(Arrow),
+}
+
+#[test]
+fn marked_field() {
+    let marked = Marked {
+        other: Span::new(0, 9),
+        range: Span::new(2, 4),
+    };
+    assert_eq!(marked.span(), Span::new(2, 4));
+}
+
+#[test]
+fn field_named_span() {
+    let named = Named {
+        inner: Span::new(0, 1),
+        span: Span::new(3, 5),
+    };
+    assert_eq!(named.span(), Span::new(3, 5));
+}
+
+#[test]
+fn only_span_field() {
+    let typed = Typed {
+        name: "typed",
+        at: Span::new(1, 2),
+        to: Some(Span::new(6, 7)),
+    };
+    assert_eq!(typed.span(), Span::new(1, 2));
+}
+
+#[test]
+fn merged_fields() {
+    let merged = Merged(
+        Some(Span::new(4, 5)),
+        vec![Span::new(1, 2), Span::new(7, 8)],
+    );
+    assert_eq!(merged.span(), Span::new(1, 8));
+}
+
+#[test]
+fn enum_variants() {
+    let left = Either::Left(Named {
+        inner: Span::new(0, 1),
+        span: Span::new(1, 2),
+    });
+    assert_eq!(left.span(), Span::new(1, 2));
+
+    let right = Either::Right {
+        outer: Span::new(0, 6),
+        inner: Span::new(2, 3),
+    };
+    assert_eq!(right.span(), Span::new(0, 6));
+
+    let both = Either::Both(
+        Span::new(0, 1),
+        Typed {
+            name: "both",
+            at: Span::new(4, 5),
+            to: Some(Span::new(5, 6)),
+        },
+    );
+    assert_eq!(both.span(), Span::new(4, 5));
+}
+
+#[test]
+fn tuple_span_at_any_index() {
+    let indexed = Indexed("indexed", vec![Span::new(0, 1)], Span::new(4, 6));
+    assert_eq!(indexed.span(), Span::new(4, 6));
+
+    let typed = Tuples::Typed('x', Span::new(1, 2));
+    assert_eq!(typed.span(), Span::new(1, 2));
+
+    // No element is a `Span`, so they are all merged.
+    let merged = Tuples::Merged(Some(Span::new(3, 4)), vec![Span::new(7, 9)]);
+    assert_eq!(merged.span(), Span::new(3, 9));
+}
+
+#[test]
+fn skipped_fields() {
+    let skipped = Skipped {
+        open: Some(Span::new(2, 3)),
+        marker: PhantomData,
+        cached: String::from("cached"),
+        close: Some(Span::new(8, 9)),
+    };
+    assert_eq!(skipped.span(), Span::new(2, 9));
+}
+
+#[test]
+fn empty_variants() {
+    assert_eq!(Marker::Value(Span::new(3, 4)).span(), Span::new(3, 4));
+    assert_eq!(Marker::Missing.span(), Span::empty(0));
+    assert_eq!(Marker::Nothing {}.span(), Span::empty(0));
+}
+
+#[test]
+fn generic_types() {
+    let wrapper = Wrapper {
+        inner: Span::new(1, 3),
+    };
+    assert_eq!(wrapper.span(), Span::new(1, 3));
+    assert_eq!(Wrapper { inner: wrapper }.span(), Span::new(1, 3));
+
+    let pair = Pair(
+        Some(Span::new(2, 3)),
+        vec![Span::new(5, 6), Span::new(9, 10)],
+    );
+    assert_eq!(pair.span(), Span::new(2, 10));
+
+    // `String` is not `Spanned`, but is only used in a skipped field.
+    let tagged = Tagged::<Span, String> {
+        value: Box::new(Span::new(4, 7)),
+        marker: PhantomData,
+    };
+    assert_eq!(tagged.span(), Span::new(4, 7));
+}
+
+#[test]
+fn lex_literal_tokens() {
+    let src = SourceFile::dummy_file("=>@=");
+    let mut input = src.iter();
+
+    assert!(Arrow::peek(&input));
+    assert!(!At::peek(&input));
+    assert_eq!(Arrow::lex(&mut input).unwrap().span(), Span::new(0, 2));
+
+    assert!(At::peek(&input));
+    assert_eq!(At::lex(&mut input).unwrap().span(), Span::new(2, 3));
+
+    // Only half of `=>` is left, so nothing is consumed.
+    assert!(!Arrow::peek(&input));
+    assert!(Arrow::lex(&mut input).is_err());
+    assert_eq!(input.offset(), 3);
+}
+
+#[test]
+fn lex_priority() {
+    let src = SourceFile::dummy_file("=>");
+
+    // `=` is declared first, and is a prefix of `=>`.
+    let declared = Declared::lex(&mut src.iter()).unwrap();
+    assert!(matches!(declared, Declared::Equals(_)));
+    assert_eq!(declared.span(), Span::new(0, 1));
+
+    let prioritised = Prioritised::lex(&mut src.iter()).unwrap();
+    assert!(matches!(prioritised, Prioritised::Arrow(_)));
+    assert_eq!(prioritised.span(), Span::new(0, 2));
+
+    let src = SourceFile::dummy_file("=");
+    assert!(matches!(
+        Prioritised::lex(&mut src.iter()),
+        Ok(Prioritised::Equals(_))
+    ));
+}
+
+#[test]
+fn lex_ignoring_case() {
+    for source in ["nan", "NaN", "NAN", "nAn"] {
+        let src = SourceFile::dummy_file(format!("{source},"));
+        let mut input = src.iter();
+
+        assert!(AnyCaseNaN::peek(&input), "{source:?} should match");
+        let nan = AnyCaseNaN::lex(&mut input).unwrap();
+        assert_eq!(nan.span(), Span::new(0, 3));
+        assert_eq!(nan.raw(&src), source);
+    }
+
+    for source in ["na", "nab", "", " nan"] {
+        let src = SourceFile::dummy_file(source);
+        assert!(
+            !AnyCaseNaN::peek(&src.iter()),
+            "{source:?} should not match"
+        );
+        assert!(AnyCaseNaN::lex(&mut src.iter()).is_err());
+    }
+}
+
+#[test]
+fn lex_multi_character_literals() {
+    let src = SourceFile::dummy_file("Infinity]");
+    let mut input = src.iter();
+    let infinity = Infinity::lex(&mut input).unwrap();
+    assert_eq!(infinity.span(), Span::new(0, 8));
+    assert_eq!(infinity.raw(&src), "Infinity");
+    assert_eq!(input.peek(), Some(']'));
+
+    for source in ["Infinit", "infinity", "Inf"] {
+        let src = SourceFile::dummy_file(source);
+        assert!(!Infinity::peek(&src.iter()), "{source:?} should not match");
+    }
+
+    // Spans count characters, not bytes.
+    let src = SourceFile::dummy_file("∞→∞");
+    let arrows = Arrows::lex(&mut src.iter()).unwrap();
+    assert_eq!(arrows.span(), Span::new(0, 2));
+    assert_eq!(arrows.raw(&src), "∞→");
+}
+
+#[test]
+fn lex_keywords() {
+    for source in ["null", "null,", "null ", "null}", "null//"] {
+        let src = SourceFile::dummy_file(source);
+        let mut input = src.iter();
+        assert!(NullKeyword::peek(&input), "{source:?} should match");
+        assert_eq!(
+            NullKeyword::lex(&mut input).unwrap().span(),
+            Span::new(0, 4)
+        );
+    }
+
+    for source in ["nullable", "null_", "null$", "null1", "nul"] {
+        let src = SourceFile::dummy_file(source);
+        let mut input = src.iter();
+        assert!(!NullKeyword::peek(&input), "{source:?} should not match");
+        assert!(NullKeyword::lex(&mut input).is_err());
+        assert_eq!(input.offset(), 0);
+    }
+}
@@ -0,0 +1,184 @@
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    lex::{
+        escapes::{
+            combine_surrogates, EscapeSequence, HexEscapeSequence, LineContinuation, NonEscapeChar,
+            SingleEscapeChar, UnicodeEscapeSequence,
+        },
+        Lex, LexError,
+    },
+};
+
+///
+/// Lex an escape sequence (without its backslash)
+/// which should cover the whole of `source`.
+///
+fn lex(source: &str) -> Result<EscapeSequence, LexError> {
+    let src = SourceFile::dummy_file(source);
+    let mut input = src.iter();
+    let escape = EscapeSequence::lex(&mut input)?;
+    assert!(input.is_eof(), "{source:?} was not fully lexed");
+    Ok(escape)
+}
+
+#[test]
+fn decode_single() {
+    let escape = lex("n").unwrap();
+    assert!(matches!(escape, EscapeSequence::Single(_)));
+    assert_eq!(escape.decode(), Some('\n'));
+    assert_eq!(lex("t").unwrap().decode(), Some('\t'));
+    assert_eq!(lex("'").unwrap().decode(), Some('\''));
+}
+
+#[test]
+fn decode_non_escape() {
+    let escape = lex("q").unwrap();
+    assert!(matches!(escape, EscapeSequence::NonEscape(_)));
+    assert_eq!(escape.decode(), Some('q'));
+}
+
+#[test]
+fn decode_null() {
+    let escape = lex("0").unwrap();
+    assert!(matches!(escape, EscapeSequence::Null(_)));
+    assert_eq!(escape.decode(), Some('\0'));
+}
+
+#[test]
+fn decode_hex() {
+    let escape = lex("x41").unwrap();
+    assert!(matches!(escape, EscapeSequence::Hex(_)));
+    assert_eq!(escape.span(), Span::new(0, 3));
+    assert_eq!(escape.decode(), Some('A'));
+}
+
+#[test]
+fn decode_unicode() {
+    let escape = lex("u00e9").unwrap();
+    assert!(matches!(escape, EscapeSequence::Unicode(_)));
+    assert_eq!(escape.span(), Span::new(0, 5));
+    assert_eq!(escape.decode(), Some('é'));
+
+    // Half of a surrogate pair.
+    assert_eq!(lex("uD83D").unwrap().decode(), None);
+    assert_eq!(lex("uDCA9").unwrap().decode(), None);
+}
+
+#[test]
+fn single_escape_table() {
+    let table = [
+        ('b', '\u{8}'),
+        ('t', '\u{9}'),
+        ('n', '\u{A}'),
+        ('v', '\u{B}'),
+        ('f', '\u{C}'),
+        ('r', '\u{D}'),
+        ('"', '"'),
+        ('\'', '\''),
+        ('\\', '\\'),
+    ];
+
+    for (raw, resolved) in table {
+        let src = SourceFile::dummy_file(raw);
+        let escape = SingleEscapeChar::lex(&mut src.iter()).unwrap();
+        assert_eq!(escape.raw_char(), raw);
+        assert_eq!(escape.resolve(), resolved, "\\{raw}");
+    }
+
+    // Not single escape characters.
+    for raw in ["a", "0", "x", "u", "V"] {
+        assert!(!SingleEscapeChar::peek(&SourceFile::dummy_file(raw).iter()));
+    }
+}
+
+#[test]
+fn null_and_digits() {
+    assert_eq!(lex("0").unwrap().decode(), Some('\0'));
+
+    // `\0` cannot be followed by another digit, and other digits cannot be escaped.
+    for source in ["00", "01", "1", "9"] {
+        assert!(lex(source).is_err(), "\\{source} should not lex");
+    }
+}
+
+#[test]
+fn error_variants() {
+    assert_eq!(
+        lex("1").unwrap_err(),
+        LexError::InvalidEscape {
+            span: Span::new(0, 1)
+        }
+    );
+
+    let error = lex("x4").unwrap_err();
+    assert!(matches!(error, LexError::Expected { .. }), "{error:?}");
+    assert_eq!(error.span(), Span::new(0, 2));
+    assert_eq!(error.message(), "expected 2 hexadecimal digits after `\\x`");
+
+    assert_eq!(
+        lex("").unwrap_err(),
+        LexError::UnexpectedEof {
+            span: Span::empty(0)
+        }
+    );
+}
+
+#[test]
+fn non_escape_resolves_to_itself() {
+    for raw in ["a", "V", "é", "💩", "-"] {
+        let src = SourceFile::dummy_file(raw);
+        let escape = NonEscapeChar::lex(&mut src.iter()).unwrap();
+        assert_eq!(escape.resolve().to_string(), raw);
+    }
+}
+
+#[test]
+fn surrogates() {
+    assert_eq!(combine_surrogates(0xD83D, 0xDCA9), Some('💩'));
+    assert_eq!(combine_surrogates(0xD800, 0xDC00), Some('\u{10000}'));
+    assert_eq!(combine_surrogates(0xDBFF, 0xDFFF), Some('\u{10FFFF}'));
+
+    // Lone, or in the wrong order.
+    assert_eq!(combine_surrogates(0xD83D, 0x0041), None);
+    assert_eq!(combine_surrogates(0x0041, 0xDCA9), None);
+    assert_eq!(combine_surrogates(0xDCA9, 0xD83D), None);
+    assert_eq!(combine_surrogates(0xD83D, 0xD83D), None);
+}
+
+#[test]
+fn hex_as_char() {
+    for (source, expected) in [("x41", 'A'), ("xFF", 'ÿ'), ("xff", 'ÿ'), ("x00", '\0')] {
+        let src = SourceFile::dummy_file(source);
+        let escape = HexEscapeSequence::lex(&mut src.iter()).unwrap();
+        assert_eq!(escape.as_char(), expected);
+    }
+}
+
+#[test]
+fn unicode_code_unit() {
+    for (source, expected) in [("u0041", 0x41), ("uD83D", 0xD83D), ("uffff", 0xFFFF)] {
+        let src = SourceFile::dummy_file(source);
+        let escape = UnicodeEscapeSequence::lex(&mut src.iter()).unwrap();
+        assert_eq!(escape.code_unit(), expected);
+    }
+}
+
+#[test]
+fn line_continuations() {
+    for source in ["\\\n", "\\\r\n", "\\\r", "\\\u{2028}", "\\\u{2029}"] {
+        let src = SourceFile::dummy_file(source);
+        let mut input = src.iter();
+        let continuation = LineContinuation::lex(&mut input).unwrap();
+        assert!(input.is_eof(), "{source:?} was not fully lexed");
+        assert_eq!(continuation.span(), Span::new(0, source.chars().count()));
+    }
+
+    // Only one line terminator is continued over.
+    let src = SourceFile::dummy_file("\\\n\n");
+    let continuation = LineContinuation::lex(&mut src.iter()).unwrap();
+    assert_eq!(continuation.span(), Span::new(0, 2));
+
+    assert!(!LineContinuation::peek(
+        &SourceFile::dummy_file("\\n").iter()
+    ));
+}
@@ -0,0 +1,176 @@
+use avjason::{
+    common::{SourceFile, Span},
+    parse::{
+        events::{Scalar, Visitor},
+        parse, parse_events, parse_events_with, DuplicateKeys, ParseError, ParseOptions,
+    },
+};
+
+///
+/// Every event, written out as a string.
+///
+#[derive(Default)]
+struct Log(Vec<String>);
+
+impl Visitor for Log {
+    fn begin_object(&mut self, open: Span) {
+        self.0.push(format!("{{ {}", range(open)));
+    }
+
+    fn key(&mut self, name: &str, span: Span) {
+        self.0.push(format!("key {name} {}", range(span)));
+    }
+
+    fn end_object(&mut self, close: Span) {
+        self.0.push(format!("}} {}", range(close)));
+    }
+
+    fn begin_array(&mut self, open: Span) {
+        self.0.push(format!("[ {}", range(open)));
+    }
+
+    fn end_array(&mut self, close: Span) {
+        self.0.push(format!("] {}", range(close)));
+    }
+
+    fn scalar(&mut self, value: Scalar<'_>, span: Span) {
+        self.0.push(format!("{value:?} {}", range(span)));
+    }
+}
+
+///
+/// `span` as `start..end`, for shorter expected logs.
+///
+fn range(span: Span) -> String {
+    format!("{}..{}", span.start(), span.end())
+}
+
+///
+/// Parse `source` with `options`, logging every event.
+///
+fn events(source: &str, options: &ParseOptions) -> Result<Vec<String>, ParseError> {
+    let mut log = Log::default();
+    parse_events_with(&SourceFile::dummy_file(source), options, &mut log)?;
+    Ok(log.0)
+}
+
+#[test]
+fn counting_without_a_tree() {
+    #[derive(Default)]
+    struct Count {
+        objects: usize,
+        arrays: usize,
+        scalars: usize,
+    }
+
+    impl Visitor for Count {
+        fn begin_object(&mut self, _: Span) {
+            self.objects += 1;
+        }
+
+        fn begin_array(&mut self, _: Span) {
+            self.arrays += 1;
+        }
+
+        fn scalar(&mut self, _: Scalar<'_>, _: Span) {
+            self.scalars += 1;
+        }
+    }
+
+    let src = SourceFile::dummy_file(
+        "{ a: [1, {}, [[]], { b: null }], c: 'd', // comment\n e: [true,], }",
+    );
+    let mut count = Count::default();
+    parse_events(&src, &mut count).unwrap();
+    assert_eq!(count.objects, 3);
+    assert_eq!(count.arrays, 4);
+    assert_eq!(count.scalars, 4);
+}
+
+#[test]
+fn events_in_order() {
+    assert_eq!(
+        events("{ a: [1, 'two'], \"b\": null }", &ParseOptions::default()).unwrap(),
+        [
+            "{ 0..1",
+            "key a 2..3",
+            "[ 5..6",
+            "Number(1.0) 6..7",
+            "String(\"two\") 9..14",
+            "] 14..15",
+            "key b 17..20",
+            "Null 22..26",
+            "} 27..28",
+        ]
+    );
+    assert_eq!(
+        events("false", &ParseOptions::default()).unwrap(),
+        ["Bool(false) 0..5"]
+    );
+}
+
+#[test]
+fn same_errors_as_the_tree() {
+    for source in [
+        "",
+        "[1 2]",
+        "[,]",
+        "{ a 1 }",
+        "{ 1: 2 }",
+        "[1] 2",
+        "[nope]",
+        "'\\x4'",
+        "[1, /* unterminated",
+    ] {
+        let src = SourceFile::dummy_file(source);
+        assert_eq!(
+            parse_events(&src, &mut Log::default()),
+            parse(&src).map(drop),
+            "{source:?}"
+        );
+    }
+}
+
+#[test]
+fn options() {
+    let deep = ParseOptions {
+        max_depth: 2,
+        ..ParseOptions::default()
+    };
+    assert!(events("[[1]]", &deep).is_ok());
+    assert_eq!(
+        events("[[[1]]]", &deep),
+        Err(ParseError::DepthExceeded {
+            span: Span::new(2, 3)
+        })
+    );
+
+    let error = ParseOptions {
+        duplicate_keys: DuplicateKeys::Error,
+        ..ParseOptions::default()
+    };
+    assert_eq!(
+        events("{ a: 1, 'a': 2 }", &error),
+        Err(ParseError::DuplicateKey {
+            span: Span::new(8, 11),
+            first: Span::new(2, 3),
+            name: "a".into(),
+        })
+    );
+
+    let first_wins = ParseOptions {
+        duplicate_keys: DuplicateKeys::FirstWins,
+        ..ParseOptions::default()
+    };
+    assert_eq!(
+        events("{ a: 1, a: [{ a: 2 }], b: 3 }", &first_wins).unwrap(),
+        [
+            "{ 0..1",
+            "key a 2..3",
+            "Number(1.0) 5..6",
+            "key b 23..24",
+            "Number(3.0) 26..27",
+            "} 28..29",
+        ]
+    );
+}
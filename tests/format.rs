@@ -0,0 +1,167 @@
+use avjason::{
+    common::SourceFile,
+    format::{
+        reformat, to_string_compact, to_string_compact_with, to_string_pretty, FormatOptions,
+    },
+    lex::strings::QuoteStyle,
+    parse::parse,
+    value::Value,
+};
+
+fn value(source: &str) -> Value {
+    parse(&SourceFile::dummy_file(source)).unwrap()
+}
+
+const DOCUMENT: &str = r#"
+    // A config file.
+    {
+        name: 'avjason', "two words": "it's",
+        numbers: [0, -1.5, 8000, 1000000, 1e300, 0.000001, Infinity, NaN],
+        nested: { empty: {}, list: [[], [null, true]] },
+    }
+"#;
+
+#[test]
+fn golden() {
+    let expected = r#"{
+  name: "avjason",
+  "two words": "it's",
+  numbers: [
+    0,
+    -1.5,
+    8000,
+    1000000,
+    1e300,
+    0.000001,
+    Infinity,
+    NaN
+  ],
+  nested: {
+    empty: {},
+    list: [
+      [],
+      [
+        null,
+        true
+      ]
+    ]
+  }
+}"#;
+    // Pretty output writes every number out in full.
+    let expected = expected.replace("1e300", &format!("1{}", "0".repeat(300)));
+
+    let value = value(DOCUMENT);
+    let pretty = to_string_pretty(&value, &FormatOptions::default());
+    assert_eq!(pretty, expected);
+
+    // `NaN` is never equal to itself, so compare the output instead.
+    let reparsed = self::value(&pretty);
+    assert_eq!(
+        to_string_pretty(&reparsed, &FormatOptions::default()),
+        expected
+    );
+}
+
+#[test]
+fn options() {
+    let options = FormatOptions {
+        indent: 4,
+        quote_keys: true,
+        quote: QuoteStyle::Single,
+        ..FormatOptions::default()
+    };
+
+    assert_eq!(
+        to_string_pretty(&value("{ a: ['b', \"it's\"] }"), &options),
+        "{\n    'a': [\n        'b',\n        'it\\'s'\n    ]\n}"
+    );
+    assert_eq!(to_string_pretty(&value("[]"), &options), "[]");
+    assert_eq!(to_string_pretty(&value("'top'"), &options), "'top'");
+}
+
+#[test]
+fn compact() {
+    let compact = to_string_compact(&value(DOCUMENT));
+    assert_eq!(
+        compact,
+        r#"{name:"avjason","two words":"it's",numbers:[0,-1.5,8e3,1e6,1e300,1e-6,Infinity,NaN],nested:{empty:{},list:[[],[null,true]]}}"#
+    );
+    assert_eq!(to_string_compact(&value(&compact)), compact);
+
+    let value = value("{ 'a': [0.5, -0.25, 10.5, 1000, 1000000, 123456789] }");
+    let compact = to_string_compact(&value);
+    assert_eq!(compact, "{a:[.5,-.25,10.5,1e3,1e6,123456789]}");
+    assert_eq!(self::value(&compact), value);
+}
+
+#[test]
+fn shortest_quotes() {
+    let value = value(r#"['plain', "it's", 'say "hi"', "both ' \""]"#);
+    let options = |quote| FormatOptions {
+        quote,
+        shortest_quotes: true,
+        ..FormatOptions::default()
+    };
+
+    let double = to_string_compact_with(&value, &options(QuoteStyle::Double));
+    assert_eq!(double, r#"["plain","it's",'say "hi"',"both ' \""]"#);
+    assert_eq!(self::value(&double), value);
+
+    let single = to_string_compact_with(&value, &options(QuoteStyle::Single));
+    assert_eq!(single, r#"['plain',"it's",'say "hi"','both \' "']"#);
+    assert_eq!(self::value(&single), value);
+}
+
+#[test]
+fn keeps_comments() {
+    let source = "// Header comment.
+
+/* before */ {
+  name:   'avjason',  // the name
+    list: [1, /* one */
+      2,
+
+
+      // before three
+      3, // three
+      // after three
+    ],
+  empty: {
+  },
+  dangling: { // only this
+  },
+  key /* inside */ : 0x10
+} // done
+// the end
+";
+
+    let expected = "// Header comment.
+
+/* before */
+{
+  name: 'avjason', // the name
+  list: [
+    1, /* one */
+    2,
+
+    // before three
+    3 // three
+    // after three
+  ],
+  empty: {},
+  dangling: {
+    // only this
+  },
+  /* inside */
+  key: 0x10
+} // done
+// the end
+";
+
+    let format =
+        |source: &str| reformat(&SourceFile::dummy_file(source), &FormatOptions::default());
+    assert_eq!(format(source).unwrap(), expected);
+
+    // Reformatting again changes nothing.
+    assert_eq!(format(expected).unwrap(), expected);
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use std::borrow::Cow;
+
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    lex::{
+        identifier::{IdentifierName, Keyword, LIdentifier},
+        Lex, LexError,
+    },
+};
+
+use self::common::alloc::counting;
+
+///
+/// Lex an identifier which should cover the whole of `source`,
+/// returning its decoded name.
+///
+fn name(source: &str) -> Result<String, LexError> {
+    let src = SourceFile::dummy_file(source);
+    let mut input = src.iter();
+    let identifier = LIdentifier::lex(&mut input)?;
+    assert!(input.is_eof(), "{source:?} was not fully lexed");
+    Ok(identifier.name(&src).into_owned())
+}
+
+#[test]
+fn plain_names() {
+    assert_eq!(name("abc").unwrap(), "abc");
+    assert_eq!(name("$_a1").unwrap(), "$_a1");
+    assert_eq!(name("été").unwrap(), "été");
+}
+
+#[test]
+fn escaped_names() {
+    assert_eq!(name(r"\u0061bc").unwrap(), "abc");
+    assert_eq!(name(r"a\u0062c").unwrap(), "abc");
+    assert_eq!(name(r"\u0061\u0062\u0063").unwrap(), "abc");
+    assert_eq!(name(r"a\u0030").unwrap(), "a0");
+}
+
+#[test]
+fn invalid_escapes() {
+    // A space is never part of an identifier.
+    let error = name(r"\u0020abc").unwrap_err();
+    assert_eq!(error.span(), Span::new(0, 6));
+
+    let error = name(r"ab\u0020").unwrap_err();
+    assert_eq!(error.span(), Span::new(2, 8));
+
+    // Digits can continue an identifier, but not start one.
+    assert!(name(r"\u0030abc").is_err());
+
+    // Surrogates are never valid.
+    assert!(name(r"a\uD83D\uDCA9").is_err());
+}
+
+#[test]
+fn escaped_identifier_start() {
+    let start =
+        |source: &str| LIdentifier::is_identifier_start(&SourceFile::dummy_file(source).iter());
+
+    // The escape is checked after the backslash, not at it.
+    assert!(start(r"\u0041foo"));
+    assert!(!start(r"\x41foo"));
+    assert!(!start(r"\foo"));
+    assert!(!start(r"\u004"));
+    assert!(!start(r"\"));
+}
+
+#[test]
+fn keywords() {
+    let keyword = |source: &str| {
+        let src = SourceFile::dummy_file(source);
+        LIdentifier::lex(&mut src.iter()).unwrap().as_keyword(&src)
+    };
+
+    assert_eq!(keyword("true"), Some(Keyword::True));
+    assert_eq!(keyword("false"), Some(Keyword::False));
+    assert_eq!(keyword("null"), Some(Keyword::Null));
+
+    for source in ["truee", "True", "nul", "NULL", r"tru\u0065"] {
+        assert_eq!(keyword(source), None, "{source}");
+    }
+
+    assert_eq!(Keyword::Null.as_str(), "null");
+}
+
+#[test]
+fn plain_names_are_borrowed() {
+    let src = SourceFile::dummy_file(r"{ plain: 1, \u0065scaped: 2 }");
+    let text = src.text().as_bytes().as_ptr_range();
+
+    let mut input = src.iter();
+    input.nth(1);
+    let plain = LIdentifier::lex(&mut input).unwrap();
+    assert_eq!(plain.as_str(&src), "plain");
+    assert!(
+        text.contains(&plain.as_str(&src).as_ptr()),
+        "should borrow from the source"
+    );
+
+    input.nth(4);
+    let escaped = LIdentifier::lex(&mut input).unwrap();
+    assert_eq!(escaped.as_str(&src), "escaped");
+    assert!(!text.contains(&escaped.as_str(&src).as_ptr()));
+
+    assert!(matches!(plain.name(&src), Cow::Borrowed("plain")));
+    assert!(matches!(escaped.name(&src), Cow::Owned(name) if name == "escaped"));
+}
+
+#[test]
+fn plain_names_do_not_allocate() {
+    let src = SourceFile::dummy_file("plain_ascii_name: 1");
+
+    let ((token, ident), counts) = counting(|| {
+        let token = LIdentifier::lex(&mut src.iter()).unwrap();
+        let ident = IdentifierName::lex(&mut src.iter()).unwrap();
+        (token, ident)
+    });
+
+    assert_eq!(
+        counts.allocations, 0,
+        "lexing {token:?} and {ident:?} allocated"
+    );
+    assert_eq!(ident.as_str(), "plain_ascii_name");
+    assert_eq!(ident.span(), Span::new(0, 16));
+}
+
+#[test]
+fn names_without_the_source() {
+    let src = SourceFile::dummy_file(r"plain \u0065scaped");
+    let mut input = src.iter();
+
+    let plain = IdentifierName::lex(&mut input).unwrap();
+    input.next();
+    let escaped = LIdentifier::lex(&mut input).unwrap().with_source(&src);
+
+    // Only these need `src` from here on.
+    let names: Vec<&str> = [&plain, &escaped].map(IdentifierName::as_str).into();
+    assert_eq!(names, ["plain", "escaped"]);
+    assert_eq!(escaped.span(), Span::new(6, 18));
+
+    assert!(matches!(plain.into_name(), Cow::Borrowed("plain")));
+    assert!(matches!(escaped.into_name(), Cow::Owned(name) if name == "escaped"));
+
+    let src = SourceFile::dummy_file("1");
+    assert!(!IdentifierName::peek(&src.iter()));
+    assert!(IdentifierName::lex(&mut src.iter()).is_err());
+}
@@ -0,0 +1,128 @@
+use std::ops::Range;
+
+use avjason::{
+    common::{SourceFile, Spanned},
+    lex::{
+        incremental::{relex, Edit, Relexed},
+        lex_all_recovering, InputElement, LexError,
+    },
+};
+
+///
+/// Replace the characters in `range` of `before` with `replacement`,
+/// checking that re-lexing just the edit gives the same elements
+/// as lexing the new text from scratch.
+///
+fn edit(
+    before: &str,
+    range: Range<usize>,
+    replacement: &str,
+) -> (SourceFile, Vec<InputElement>, Relexed) {
+    let old = SourceFile::dummy_file(before);
+    let (mut elements, _) = lex_all_recovering(&old);
+
+    let mut chars: Vec<char> = before.chars().collect();
+    chars.splice(range.clone(), replacement.chars());
+    let new = SourceFile::dummy_file(chars.into_iter().collect::<String>());
+
+    let edit = Edit {
+        range,
+        inserted: replacement.chars().count(),
+    };
+    let relexed = relex(&new, &mut elements, &edit);
+
+    let (expected, expected_errors) = lex_all_recovering(&new);
+    assert_eq!(elements, expected, "re-lexing {:?}", new.text());
+    assert!(relexed
+        .errors
+        .iter()
+        .all(|error| expected_errors.contains(error)));
+
+    (new, elements, relexed)
+}
+
+#[test]
+fn edit_inside_string() {
+    let (src, elements, relexed) = edit("{ a: 'hello', b: [1, 2] }", 7..10, "ipp");
+    assert_eq!(src.text(), "{ a: 'hippo', b: [1, 2] }");
+    assert_eq!(elements[5].raw(&src), "'hippo'");
+
+    // Just the string, and the whitespace before it.
+    assert_eq!(relexed.elements, 4..6);
+    assert!(relexed.errors.is_empty());
+}
+
+#[test]
+fn edit_between_tokens() {
+    let (src, elements, relexed) = edit("[1, 2]", 5..5, ", 3");
+    assert_eq!(src.text(), "[1, 2, 3]");
+
+    let raw: Vec<_> = elements.iter().map(|element| element.raw(&src)).collect();
+    assert_eq!(raw, ["[", "1", ",", " ", "2", ",", " ", "3", "]"]);
+    // From the whitespace before the `2` up to the `]`, which is kept.
+    assert_eq!(relexed.elements, 3..8);
+
+    // Deleting it again.
+    let (src, _, relexed) = edit("[1, 2, 3]", 5..8, "");
+    assert_eq!(src.text(), "[1, 2]");
+    assert_eq!(relexed.elements, 3..5);
+}
+
+#[test]
+fn later_elements_are_shifted() {
+    let (src, elements, _) = edit("[é, 'a', 💩]\n// end", 1..2, "long");
+    let last = elements.last().unwrap();
+    assert_eq!(last.raw(&src), "// end");
+    assert_eq!(last.span().start(), 15);
+}
+
+#[test]
+fn edits_which_join_or_split_tokens() {
+    // Joining two identifiers.
+    let (src, elements, _) = edit("[ab cd]", 3..4, "");
+    assert_eq!(elements[1].raw(&src), "abcd");
+
+    // Splitting a number.
+    edit("[12345]", 3..3, " ");
+
+    // Finishing a decimal point.
+    edit("[1. ]", 3..3, "5");
+}
+
+#[test]
+fn edits_which_change_the_rest_of_the_file() {
+    // An unterminated comment is an error, and lexing recovers at the next
+    // whitespace, which is where the old elements line up again.
+    let (_, elements, relexed) = edit("[1, 2, 3] // x", 1..1, "/*");
+    assert_eq!(relexed.elements, 0..1);
+    assert_eq!(elements.len(), 9);
+    assert!(matches!(
+        relexed.errors[..],
+        [LexError::UnterminatedComment { .. }]
+    ));
+
+    // Opening a string makes it unterminated at the end of the line.
+    let (_, _, relexed) = edit("{ a: 1, b: 2 }\n{}", 5..5, "'");
+    assert!(matches!(
+        relexed.errors[..],
+        [LexError::UnterminatedString { .. }]
+    ));
+}
+
+#[test]
+fn edits_after_an_error() {
+    // Lexing recovers from the unterminated comment on the next line,
+    // but the edit closes it, so the first line lexes differently too.
+    let (_, elements, relexed) = edit("/* 1\n2 /] 3", 8..9, "*/");
+    assert_eq!(relexed.elements, 0..1);
+    assert!(matches!(elements[0], InputElement::Comment(_)));
+    assert_eq!(elements.len(), 3);
+}
+
+#[test]
+fn edits_at_either_end() {
+    edit("[1]", 0..0, "  ");
+    edit("[1]", 3..3, "\n");
+    edit("[1]", 0..3, "");
+    edit("", 0..0, "{}");
+}
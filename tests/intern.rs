@@ -0,0 +1,36 @@
+use avjason::{
+    common::SourceFile,
+    intern::Interner,
+    lex::{identifier::LIdentifier, Lex},
+};
+
+///
+/// Lex every space-separated identifier in `src`.
+///
+fn identifiers(src: &SourceFile) -> Vec<LIdentifier> {
+    let mut input = src.iter();
+    let mut identifiers = vec![];
+    while !input.is_eof() {
+        identifiers.push(LIdentifier::lex(&mut input).unwrap());
+        input.next();
+    }
+
+    identifiers
+}
+
+#[test]
+fn equal_names_share_a_symbol() {
+    let src = SourceFile::dummy_file(r"key \u006bey other key");
+    let ids = identifiers(&src);
+    let mut interner = Interner::new();
+
+    let symbols: Vec<_> = ids.iter().map(|id| interner.intern(id, &src)).collect();
+    assert_eq!(symbols[0], symbols[1]);
+    assert_eq!(symbols[0], symbols[3]);
+    assert_ne!(symbols[0], symbols[2]);
+
+    assert_eq!(interner.len(), 2);
+    assert_eq!(interner.resolve(symbols[1]), "key");
+    assert_eq!(interner.resolve(symbols[2]), "other");
+    assert_eq!(interner.intern_str("other"), symbols[2]);
+}
@@ -0,0 +1,96 @@
+use std::error::Error;
+
+mod common;
+
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    lex::{lex_all_recovering, strings::LString, Lex, LexError},
+};
+
+use self::common::alloc::counting;
+
+///
+/// The error from lexing `source` as a string literal.
+///
+fn string_error(source: &str) -> LexError {
+    let src = SourceFile::dummy_file(source);
+    LString::lex(&mut src.iter()).unwrap_err()
+}
+
+#[test]
+fn display() {
+    let error = string_error("'ab\\q\tc'");
+    assert_eq!(
+        error.to_string(),
+        "control character U+0009 must be escaped in a string literal at 5..6"
+    );
+
+    let error = string_error("'abc");
+    assert_eq!(error.to_string(), "unterminated string literal at 0..4");
+}
+
+#[test]
+fn into_boxed_error() {
+    fn lex_string(source: &str) -> Result<LString, Box<dyn Error>> {
+        let src = SourceFile::dummy_file(source);
+        Ok(LString::lex(&mut src.iter())?)
+    }
+
+    assert!(lex_string("'ok'").is_ok());
+
+    let error = lex_string("'\\x4'").unwrap_err();
+    assert!(error.to_string().ends_with(" at 2..4"), "{error}");
+    assert!(error.downcast_ref::<LexError>().is_some());
+}
+
+#[test]
+fn recover_from_errors() {
+    let src = SourceFile::dummy_file("[1, '\\x4g', 2,\n 0123, 3]");
+    let (elements, errors) = lex_all_recovering(&src);
+
+    assert!(matches!(
+        errors.as_slice(),
+        [LexError::Expected { .. }, LexError::LegacyOctal { .. }]
+    ));
+    assert_eq!(errors[1].span(), Span::new(16, 20));
+
+    let tokens: Vec<_> = elements
+        .iter()
+        .filter(|element| !element.is_trivia())
+        .map(|token| token.raw(&src))
+        .collect();
+    assert_eq!(tokens, ["[", "1", ",", "2", ",", "3", "]"]);
+}
+
+#[test]
+fn elements_are_reserved_up_front() {
+    let mut text = String::from("[\n");
+    for i in 0..1000 {
+        text.push_str(&format!(
+            "  {{ id: {i}, name: 'item {i}', tags: [\"a\", 0x{i:x}] }}, // #{i}\n"
+        ));
+    }
+    text.push(']');
+
+    let src = SourceFile::dummy_file(text);
+    let ((elements, errors), counts) = counting(|| lex_all_recovering(&src));
+    assert!(errors.is_empty());
+    assert!(elements.len() > 20_000);
+    assert_eq!(counts.reallocations, 0);
+}
+
+#[test]
+fn recover_at_end_of_input() {
+    let src = SourceFile::dummy_file("1 'abc");
+    let (elements, errors) = lex_all_recovering(&src);
+
+    assert_eq!(elements.len(), 2);
+    assert!(matches!(
+        errors.as_slice(),
+        [LexError::UnterminatedString { .. }]
+    ));
+
+    let (elements, errors) = lex_all_recovering(&SourceFile::dummy_file("# #"));
+    assert_eq!(elements.len(), 1);
+    assert_eq!(errors.len(), 2);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "miette")]
+
+use avjason::{
+    common::SourceFile,
+    lex::{strings::LString, Lex, LexDiagnostic},
+};
+use miette::{Diagnostic, SourceSpan};
+
+#[test]
+fn label_range() {
+    // `é` is two bytes, so the byte range is one later than the character range.
+    let src = SourceFile::dummy_file("'é\tb'");
+    let error = LString::lex(&mut src.iter()).unwrap_err();
+    let diagnostic = error.into_diagnostic(&src);
+
+    assert_eq!(diagnostic.span(), SourceSpan::from(3..4));
+    assert_eq!(
+        diagnostic.to_string(),
+        "control character U+0009 must be escaped in a string literal"
+    );
+
+    let labels: Vec<_> = diagnostic.labels().unwrap().collect();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].offset(), 3);
+    assert_eq!(labels[0].len(), 1);
+    assert_eq!(labels[0].label(), Some(diagnostic.to_string().as_str()));
+
+    assert!(diagnostic.source_code().is_some());
+}
+
+#[test]
+fn from_lex_error() {
+    fn lex(src: &SourceFile) -> Result<LString, LexDiagnostic> {
+        Ok(LString::lex(&mut src.iter())?)
+    }
+
+    let diagnostic = lex(&SourceFile::dummy_file("'a\tb'")).unwrap_err();
+    assert_eq!(diagnostic.span(), SourceSpan::from(2..3));
+    assert_eq!(
+        diagnostic.to_string(),
+        "control character U+0009 must be escaped in a string literal"
+    );
+    assert_eq!(diagnostic.labels().unwrap().count(), 1);
+    assert!(diagnostic.source_code().is_none());
+}
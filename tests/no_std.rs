@@ -0,0 +1,38 @@
+//!
+//! Run with `cargo test --no-default-features`, to check that
+//! lexing and parsing work with just `alloc`.
+//!
+
+#![cfg(not(feature = "std"))]
+
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    format::{to_string_compact, to_string_pretty, FormatOptions},
+    lex::lex_all_recovering,
+    parse::{parse, parse_cst},
+    value::{Map, Value},
+};
+
+#[test]
+fn parse_without_std() {
+    let src = SourceFile::dummy_file("{ a: [1, 'two'], // é\n  b: null }");
+
+    let value = parse(&src).unwrap();
+    let mut object = Map::default();
+    object.insert(
+        "a".into(),
+        Value::Array(vec![Value::Number(1.0), Value::String("two".into())]),
+    );
+    object.insert("b".into(), Value::Null);
+    assert_eq!(value, Value::Object(object));
+    assert_eq!(to_string_compact(&value), r#"{a:[1,"two"],b:null}"#);
+    assert!(to_string_pretty(&value, &FormatOptions::default()).contains("\n  b: null"));
+
+    let (elements, errors) = lex_all_recovering(&src);
+    assert_eq!(elements.len(), 22);
+    assert!(errors.is_empty());
+    assert!(parse_cst(&src).is_ok());
+
+    assert_eq!(src.line_index().location(24), (2, 3));
+    assert_eq!(Span::new(20, 21).raw(&src), "é");
+}
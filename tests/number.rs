@@ -0,0 +1,296 @@
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    lex::{
+        number::{Number, NumberKind, Sign},
+        tokens::{Punct, Token},
+        InputElement, Lex, LexError,
+    },
+};
+
+///
+/// Lex a number which should cover the whole of `source`.
+///
+fn lex(source: &str) -> Result<Number, LexError> {
+    let src = SourceFile::dummy_file(source);
+    let mut input = src.iter();
+    let number = Number::lex(&mut input)?;
+    assert!(input.is_eof(), "{source:?} was not fully lexed");
+    Ok(number)
+}
+
+#[test]
+fn hex_literals() {
+    for source in ["0x0", "0xFF", "0Xff", "0xDEADbeef"] {
+        let number = lex(source).unwrap();
+        assert_eq!(number.kind(), NumberKind::Hex);
+        assert_eq!(number.span(), Span::new(0, source.len()));
+    }
+
+    assert_eq!(lex("42").unwrap().kind(), NumberKind::Decimal);
+}
+
+#[test]
+fn hex_without_digits() {
+    for source in ["0x", "0X"] {
+        let err = lex(source).unwrap_err();
+        assert_eq!(err.span(), Span::new(0, 2));
+    }
+}
+
+#[test]
+fn hex_values_round_once() {
+    for (digits, value) in [
+        // Rounding `0x20000000000001` first would give `2^57` here.
+        ("200000000000011", 1.441151880758559e17),
+        // A non-zero digit past the first 32 still rounds up.
+        (
+            "200000000000010000000000000000000000000000001",
+            1.9156194260823615e53,
+        ),
+        ("1000000000000000000000000000000000", 5.444517870735016e39),
+        ("00000000000000000000000000000000000FF", 255.0),
+    ] {
+        let src = SourceFile::dummy_file(format!("0x{digits}"));
+        let number = Number::lex(&mut src.iter()).unwrap();
+        assert_eq!(number.as_f64(&src), Ok(value), "{digits:?}");
+    }
+}
+
+#[test]
+fn literals_followed_by_identifiers() {
+    for (source, at) in [
+        ("0xFG", 3),
+        ("123abc", 3),
+        ("1e5x", 3),
+        ("5.$", 2),
+        ("-1_", 2),
+    ] {
+        let err = lex(source).unwrap_err();
+        assert_eq!(err.span(), Span::new(at, at + 1), "{source:?}");
+    }
+
+    // Punctuators and whitespace may still follow straight away.
+    assert_eq!(lex_tokens("[1,0x2]").len(), 5);
+}
+
+#[test]
+fn exponents() {
+    for (source, exponent) in [
+        ("1e10", 1..4),
+        ("1.5e+3", 3..6),
+        ("6E-2", 1..4),
+        ("2.5E-3", 3..6),
+    ] {
+        let number = lex(source).unwrap();
+        assert_eq!(number.kind(), NumberKind::Decimal);
+        assert_eq!(number.exponent().unwrap().span(), Span::from(exponent));
+    }
+
+    assert!(lex("15").unwrap().exponent().is_none());
+}
+
+#[test]
+fn exponent_without_digits() {
+    for source in ["1e", "1e+", "1.5E-"] {
+        let err = lex(source).unwrap_err();
+        let e = source.find(['e', 'E']).unwrap();
+        assert_eq!(err.span(), Span::from(e..=e));
+    }
+}
+
+#[test]
+fn decimal_points() {
+    for source in [".5", "5.", "5.5", "5.e3", ".5e-1"] {
+        assert_eq!(lex(source).unwrap().kind(), NumberKind::Decimal);
+    }
+
+    let src = SourceFile::dummy_file(".");
+    let token = Token::lex(&mut src.iter()).unwrap();
+    assert!(matches!(token, Token::Punctuator(Punct::Dot(_))));
+
+    let src = SourceFile::dummy_file(".x");
+    let token = Token::lex(&mut src.iter()).unwrap();
+    assert!(matches!(token, Token::Punctuator(Punct::Dot(_))));
+}
+
+fn lex_tokens(source: &str) -> Vec<Token> {
+    let src = SourceFile::dummy_file(source);
+    let mut input = src.iter();
+    let mut tokens = vec![];
+    while !input.is_eof() {
+        tokens.push(Token::lex(&mut input).unwrap());
+    }
+    tokens
+}
+
+#[test]
+fn infinity_and_nan() {
+    assert_eq!(lex("Infinity").unwrap().kind(), NumberKind::Infinity);
+    assert_eq!(lex("NaN").unwrap().kind(), NumberKind::NaN);
+
+    let infinity = lex("-Infinity").unwrap();
+    assert_eq!(infinity.kind(), NumberKind::Infinity);
+    assert_eq!(infinity.sign(), Sign::Minus);
+    assert_eq!(infinity.span(), Span::new(0, 9));
+
+    let nan = lex("+NaN").unwrap();
+    assert_eq!(nan.kind(), NumberKind::NaN);
+    assert_eq!(nan.sign(), Sign::Plus);
+
+    for source in ["Infinityx", "NaNa", "Infinity_", "Inf"] {
+        assert!(
+            matches!(&lex_tokens(source)[..], [Token::Identifier(_)]),
+            "{source:?} should be an identifier"
+        );
+    }
+}
+
+#[test]
+fn values() {
+    for (source, value) in [
+        ("0x10", 16.0),
+        ("0xFF", 255.0),
+        ("1.5e2", 150.0),
+        ("42", 42.0),
+        (".5", 0.5),
+        ("5.", 5.0),
+        ("6E-2", 0.06),
+        ("Infinity", f64::INFINITY),
+        ("-Infinity", f64::NEG_INFINITY),
+        ("-0x10", -16.0),
+        ("+1.5", 1.5),
+        ("-.5e1", -5.0),
+    ] {
+        let src = SourceFile::dummy_file(source);
+        let number = Number::lex(&mut src.iter()).unwrap();
+        assert_eq!(number.as_f64(&src), Ok(value), "{source:?}");
+    }
+
+    let src = SourceFile::dummy_file("NaN");
+    assert!(Number::lex(&mut src.iter())
+        .unwrap()
+        .as_f64(&src)
+        .unwrap()
+        .is_nan());
+}
+
+#[test]
+fn integers() {
+    let as_i64 = |source: &str| {
+        let src = SourceFile::dummy_file(source);
+        let number = Number::lex(&mut src.iter()).unwrap();
+        (number.is_integer(), number.as_i64(&src))
+    };
+
+    assert_eq!(as_i64("42"), (true, Some(42)));
+    assert_eq!(as_i64("0xFF"), (true, Some(255)));
+    assert_eq!(as_i64("1e3"), (true, Some(1000)));
+    assert_eq!(as_i64("0e400"), (true, Some(0)));
+    assert_eq!(as_i64("0e999999999999999999"), (true, Some(0)));
+    assert_eq!(as_i64("-0E+99999999999"), (true, Some(0)));
+    assert_eq!(as_i64("9223372036854775807"), (true, Some(i64::MAX)));
+    assert_eq!(as_i64("-9223372036854775808"), (true, Some(i64::MIN)));
+    assert_eq!(as_i64("-0x10"), (true, Some(-16)));
+    assert_eq!(as_i64("+7"), (true, Some(7)));
+
+    // Integral values, but not written as integers.
+    assert_eq!(as_i64("1.0"), (false, None));
+    assert_eq!(as_i64("1.5e1"), (false, None));
+    assert_eq!(as_i64("10e-1"), (false, None));
+    assert_eq!(as_i64("Infinity"), (false, None));
+
+    // Too big.
+    assert_eq!(as_i64("9223372036854775808"), (true, None));
+    assert_eq!(as_i64("0x8000000000000000"), (true, None));
+    assert_eq!(as_i64("1e19"), (true, None));
+}
+
+#[test]
+fn unsigned_integers() {
+    let as_u64 = |source: &str| {
+        let src = SourceFile::dummy_file(source);
+        Number::lex(&mut src.iter()).unwrap().as_u64(&src)
+    };
+
+    assert_eq!(as_u64("0xFFFFFFFFFFFFFFFF"), Some(u64::MAX));
+    assert_eq!(as_u64("1e19"), Some(10_000_000_000_000_000_000));
+    assert_eq!(as_u64("-0"), Some(0));
+    assert_eq!(as_u64("-1"), None);
+    assert_eq!(as_u64("1.0"), None);
+    assert_eq!(as_u64("1e20"), None);
+}
+
+#[test]
+fn overflow() {
+    for source in ["1e400", "1.7976931348623159e308", "999e999"] {
+        let src = SourceFile::dummy_file(source);
+        let number = Number::lex(&mut src.iter()).unwrap();
+        let err = number.as_f64(&src).unwrap_err();
+        assert_eq!(err.span(), number.span());
+    }
+
+    let src = SourceFile::dummy_file(format!("0x{}", "F".repeat(257)));
+    let number = Number::lex(&mut src.iter()).unwrap();
+    assert!(number.as_f64(&src).is_err());
+
+    let src = SourceFile::dummy_file("1.7976931348623157e308");
+    let number = Number::lex(&mut src.iter()).unwrap();
+    assert_eq!(number.as_f64(&src), Ok(f64::MAX));
+
+    let src = SourceFile::dummy_file("Infinity");
+    let number = Number::lex(&mut src.iter()).unwrap();
+    assert_eq!(number.as_f64(&src), Ok(f64::INFINITY));
+}
+
+#[test]
+fn legacy_octal() {
+    for source in ["0123", "00", "007.5"] {
+        let err = lex(source).unwrap_err();
+        assert!(err.message().contains("octal"), "{source:?}");
+        assert_eq!(err.span().start(), 0);
+    }
+
+    for source in ["0", "0.5", "0x1", "0e5", "10", "100.001"] {
+        assert!(lex(source).is_ok(), "{source:?}");
+    }
+}
+
+#[test]
+fn signs() {
+    let minus = lex("-5").unwrap();
+    assert_eq!(minus.sign(), Sign::Minus);
+    assert_eq!(minus.span(), Span::new(0, 2));
+
+    let plus = lex("+5").unwrap();
+    assert_eq!(plus.sign(), Sign::Plus);
+
+    assert_eq!(lex("5").unwrap().sign(), Sign::Unsigned);
+
+    match &lex_tokens("-5")[..] {
+        [Token::Number(number)] => assert_eq!(number.sign(), Sign::Minus),
+        tokens => panic!("unexpected tokens {tokens:?}"),
+    }
+
+    // With a gap, the sign is a punctuator of its own.
+    let src = SourceFile::dummy_file("- 5");
+    let mut input = src.iter();
+    let elements: Vec<_> =
+        std::iter::from_fn(|| (!input.is_eof()).then(|| InputElement::lex(&mut input).unwrap()))
+            .collect();
+
+    match &elements[..] {
+        [InputElement::Token(Token::Punctuator(Punct::Minus(_))), InputElement::WhiteSpace(_), InputElement::Token(Token::Number(number))] =>
+        {
+            assert_eq!(number.sign(), Sign::Unsigned)
+        }
+        elements => panic!("unexpected elements {elements:?}"),
+    }
+
+    // Only one sign is part of the number.
+    match &lex_tokens("--5")[..] {
+        [Token::Punctuator(Punct::Minus(_)), Token::Number(number)] => {
+            assert_eq!(number.sign(), Sign::Minus)
+        }
+        tokens => panic!("unexpected tokens {tokens:?}"),
+    }
+}
@@ -0,0 +1,210 @@
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    parse::{parse_with, DuplicateKeys, ParseError, ParseOptions},
+    value::Value,
+};
+
+///
+/// Parse `source` into a [`Value`].
+///
+fn parse(source: &str) -> Result<Value, ParseError> {
+    avjason::parse::parse(&SourceFile::dummy_file(source))
+}
+
+fn object<const N: usize>(members: [(&str, Value); N]) -> Value {
+    Value::Object(
+        members
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    )
+}
+
+#[test]
+fn nested_values() {
+    assert_eq!(
+        parse(r#"{a:1, b:[true, null, "x"]}"#).unwrap(),
+        object([
+            ("a", Value::Number(1.0)),
+            (
+                "b",
+                Value::Array(vec![
+                    Value::Bool(true),
+                    Value::Null,
+                    Value::String("x".into())
+                ])
+            ),
+        ])
+    );
+}
+
+#[test]
+fn scalars() {
+    assert_eq!(parse("null").unwrap(), Value::Null);
+    assert_eq!(parse("false").unwrap(), Value::Bool(false));
+    assert_eq!(parse("-0x10").unwrap(), Value::Number(-16.0));
+    assert_eq!(parse("'\\u0041b'").unwrap(), Value::String("Ab".into()));
+    assert_eq!(parse("[]").unwrap(), Value::Array(vec![]));
+    assert_eq!(parse("{}").unwrap(), object([]));
+}
+
+#[test]
+fn skips_trivia() {
+    let source = "// leading\n{ /* a */ a : [ 1 , // one\n 2 ] }\n";
+    assert_eq!(
+        parse(source).unwrap(),
+        object([(
+            "a",
+            Value::Array(vec![Value::Number(1.0), Value::Number(2.0)])
+        )])
+    );
+}
+
+#[test]
+fn errors() {
+    assert_eq!(parse("").unwrap_err().message(), "expected a value");
+    assert_eq!(parse("nope").unwrap_err().message(), "expected a value");
+    assert_eq!(parse("1 2").unwrap_err().message(), "expected end of input");
+    assert_eq!(
+        parse("[1 2]").unwrap_err().message(),
+        "expected `,` or a closing bracket"
+    );
+    assert_eq!(parse("{a 1}").unwrap_err().message(), "expected `:`");
+
+    // Lex errors are passed through.
+    assert_eq!(
+        parse("['abc]").unwrap_err().message(),
+        "unterminated string literal"
+    );
+    assert_eq!(
+        parse("1e400").unwrap_err().message(),
+        "number is too large to be represented"
+    );
+}
+
+#[test]
+fn keys() {
+    let one = || object([("foo", Value::Number(1.0))]);
+    assert_eq!(parse("{foo:1}").unwrap(), one());
+    assert_eq!(parse(r#"{"foo":1}"#).unwrap(), one());
+    assert_eq!(parse("{'foo':1}").unwrap(), one());
+
+    // Escapes are decoded, in identifiers and strings alike.
+    assert_eq!(parse(r"{f\u006Fo:1}").unwrap(), one());
+    assert_eq!(parse(r"{'f\x6Fo':1}").unwrap(), one());
+
+    // Reserved words are still identifiers, and strings can hold anything.
+    assert_eq!(
+        parse("{null: 1, 'two words': 2}").unwrap(),
+        object([
+            ("null", Value::Number(1.0)),
+            ("two words", Value::Number(2.0))
+        ])
+    );
+}
+
+#[test]
+fn invalid_keys() {
+    for source in ["{1:2}", "{-1: 2}", "{[]: 2}", "{:2}"] {
+        let error = parse(source).unwrap_err();
+        assert_eq!(
+            error.message(),
+            "expected an object key, which must be an identifier or a string",
+            "{source:?}"
+        );
+    }
+
+    assert_eq!(parse("{1:2}").unwrap_err().span(), Span::new(1, 2));
+}
+
+#[test]
+fn trailing_commas() {
+    let numbers = |n: &[f64]| Value::Array(n.iter().copied().map(Value::Number).collect());
+    assert_eq!(parse("[1,2,]").unwrap(), numbers(&[1.0, 2.0]));
+    assert_eq!(parse("[1, // comment\n]").unwrap(), numbers(&[1.0]));
+    assert_eq!(
+        parse("{a:1,}").unwrap(),
+        object([("a", Value::Number(1.0))])
+    );
+
+    for source in ["[,]", "[1,,2]", "[,1]", "[1,,]", "{,}", "{a:1,,}", "{,a:1}"] {
+        let error = parse(source).unwrap_err();
+        assert_eq!(
+            error.message(),
+            "unexpected `,` without an item before it",
+            "{source:?}"
+        );
+    }
+
+    assert_eq!(parse("[1,,2]").unwrap_err().span(), Span::new(3, 4));
+}
+
+#[test]
+fn max_depth() {
+    let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+    assert!(parse(&nested(100)).is_ok());
+    assert!(parse(&nested(128)).is_ok());
+
+    let error = parse(&nested(200)).unwrap_err();
+    assert_eq!(
+        error,
+        ParseError::DepthExceeded {
+            span: Span::new(128, 129)
+        }
+    );
+    assert_eq!(error.message(), "objects and arrays are nested too deeply");
+
+    let options = ParseOptions {
+        max_depth: 2,
+        ..ParseOptions::default()
+    };
+    let parse = |source: &str| parse_with(&SourceFile::dummy_file(source), &options);
+    assert!(parse("{a: [1, 2], b: {}}").is_ok());
+    assert_eq!(
+        parse("[{a: []}]").unwrap_err(),
+        ParseError::DepthExceeded {
+            span: Span::new(5, 6)
+        }
+    );
+}
+
+#[test]
+fn duplicate_keys() {
+    let parse = |source: &str, duplicate_keys| {
+        let options = ParseOptions {
+            duplicate_keys,
+            ..ParseOptions::default()
+        };
+        parse_with(&SourceFile::dummy_file(source), &options)
+    };
+
+    assert_eq!(
+        parse("{a:1, a:2}", DuplicateKeys::Allow).unwrap(),
+        object([("a", Value::Number(2.0))])
+    );
+    assert_eq!(
+        parse("{a:1, a:2}", DuplicateKeys::FirstWins).unwrap(),
+        object([("a", Value::Number(1.0))])
+    );
+    assert_eq!(
+        parse("{a:1, a:2}", DuplicateKeys::Error).unwrap_err(),
+        ParseError::DuplicateKey {
+            span: Span::new(6, 7),
+            first: Span::new(1, 2),
+            name: "a".into(),
+        }
+    );
+
+    // Keys are compared once decoded, and only within the same object.
+    let error = parse("{a:1, b:{a:2}, 'a':3}", DuplicateKeys::Error).unwrap_err();
+    assert_eq!(error.span(), Span::new(15, 18));
+    assert_eq!(error.message(), "duplicate key \"a\"");
+    assert_eq!(
+        parse("{a:{b:1, b:2}, b:3, a:4}", DuplicateKeys::FirstWins).unwrap(),
+        object([
+            ("a", object([("b", Value::Number(1.0))])),
+            ("b", Value::Number(3.0))
+        ])
+    );
+}
@@ -0,0 +1,100 @@
+//!
+//! Formatting any [`Value`] and parsing it again should give back the same value.
+//!
+//! Values are compared with [`same`] rather than `==`, which is stricter
+//! about numbers and objects:
+//!
+//! * NaN is the same as NaN, although `NaN != NaN`;
+//! * every other number must read back with exactly the same bits,
+//!   so `-0` must stay negative, and no precision may be lost;
+//! * object members must stay in the same order.
+//!
+
+use avjason::{
+    common::SourceFile,
+    format::{to_string_compact_with, to_string_pretty, FormatOptions},
+    lex::strings::QuoteStyle,
+    parse::parse,
+    value::{Map, Value},
+};
+use proptest::prelude::*;
+
+///
+/// Whether `a` and `b` are the same, as described at the top of this file.
+///
+fn same(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => {
+            (a.is_nan() && b.is_nan()) || a.to_bits() == b.to_bits()
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|((ka, va), (kb, vb))| ka == kb && same(va, vb))
+        }
+        _ => a == b,
+    }
+}
+
+fn value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        any::<f64>().prop_map(Value::Number),
+        any::<String>().prop_map(Value::String),
+    ];
+
+    leaf.prop_recursive(4, 64, 8, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..8).prop_map(Value::Array),
+            prop::collection::vec((any::<String>(), inner), 0..8)
+                .prop_map(|members| Value::Object(members.into_iter().collect::<Map>())),
+        ]
+    })
+}
+
+fn options() -> impl Strategy<Value = FormatOptions> {
+    (
+        0..5usize,
+        any::<bool>(),
+        prop_oneof![Just(QuoteStyle::Single), Just(QuoteStyle::Double)],
+        any::<bool>(),
+    )
+        .prop_map(
+            |(indent, quote_keys, quote, shortest_quotes)| FormatOptions {
+                indent,
+                quote_keys,
+                quote,
+                shortest_quotes,
+            },
+        )
+}
+
+///
+/// Parse `text`, which was formatted from `value`, and check it reads back the same.
+///
+fn check(value: &Value, text: &str) -> Result<(), TestCaseError> {
+    let parsed = parse(&SourceFile::dummy_file(text))
+        .map_err(|err| TestCaseError::fail(format!("{err:?} in {text:?}")))?;
+    prop_assert!(
+        same(value, &parsed),
+        "{value:?} became {parsed:?} via {text:?}"
+    );
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn pretty(value in value(), options in options()) {
+        check(&value, &to_string_pretty(&value, &options))?;
+    }
+
+    #[test]
+    fn compact(value in value(), options in options()) {
+        check(&value, &to_string_compact_with(&value, &options))?;
+    }
+}
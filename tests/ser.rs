@@ -0,0 +1,111 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use avjason::{
+    de::from_str,
+    lex::strings::QuoteStyle,
+    ser::{to_string, to_string_with, SerializeOptions},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    #[serde(rename = "max-depth")]
+    max_depth: u8,
+    ratio: f64,
+    scale: f32,
+    tags: Vec<String>,
+    parent: Option<Box<Config>>,
+    shape: Shape,
+    extra: BTreeMap<u32, Shape>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Point,
+    Circle(f64),
+    Line(i32, i32),
+    Rect { width: u32, height: u32 },
+}
+
+fn config() -> Config {
+    Config {
+        name: "it's \"quoted\"\n".into(),
+        max_depth: 8,
+        ratio: 0.25,
+        scale: 0.1,
+        tags: vec!["a".into(), "b".into()],
+        parent: Some(Box::new(Config {
+            name: "root".into(),
+            max_depth: 0,
+            ratio: -1e300,
+            scale: f32::MAX,
+            tags: vec![],
+            parent: None,
+            shape: Shape::Point,
+            extra: BTreeMap::new(),
+        })),
+        shape: Shape::Circle(1.5),
+        extra: BTreeMap::from([
+            (1, Shape::Line(-1, 1)),
+            (
+                2,
+                Shape::Rect {
+                    width: 2,
+                    height: 3,
+                },
+            ),
+        ]),
+    }
+}
+
+#[test]
+fn struct_output() {
+    assert_eq!(
+        to_string(&config()).unwrap(),
+        concat!(
+            r#"{name:"it's \"quoted\"\n","max-depth":8,ratio:0.25,scale:0.1,tags:["a","b"],"#,
+            r#"parent:{name:"root","max-depth":0,ratio:-1e300,scale:3.4028235e38,tags:[],parent:null,shape:"Point",extra:{}},"#,
+            r#"shape:{Circle:1.5},extra:{"1":{Line:[-1,1]},"2":{Rect:{width:2,height:3}}}}"#,
+        )
+    );
+}
+
+#[test]
+fn round_trip() {
+    for quote in [QuoteStyle::Single, QuoteStyle::Double] {
+        let written = to_string_with(&config(), &SerializeOptions { quote }).unwrap();
+        assert_eq!(from_str::<Config>(&written), Ok(config()), "{written}");
+    }
+}
+
+#[test]
+fn scalars() {
+    assert_eq!(to_string(&1.0).unwrap(), "1.0");
+    assert_eq!(to_string(&f64::NEG_INFINITY).unwrap(), "-Infinity");
+    assert_eq!(to_string(&f64::NAN).unwrap(), "NaN");
+    assert_eq!(to_string(&0.1f32).unwrap(), "0.1");
+    assert_eq!(to_string(&f32::INFINITY).unwrap(), "Infinity");
+    assert_eq!(to_string(&u64::MAX).unwrap(), "18446744073709551615");
+    assert_eq!(to_string(&'\0').unwrap(), r#""\x00""#);
+    assert_eq!(to_string(&()).unwrap(), "null");
+
+    let options = SerializeOptions {
+        quote: QuoteStyle::Single,
+    };
+    assert_eq!(
+        to_string_with("say \"hi\"", &options).unwrap(),
+        r#"'say "hi"'"#
+    );
+}
+
+#[test]
+fn invalid_keys() {
+    let map = BTreeMap::from([(vec![1], 2)]);
+    assert_eq!(
+        to_string(&map).unwrap_err().message(),
+        "object keys cannot be arrays"
+    );
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "serde_json")]
+
+use avjason::{common::SourceFile, parse::to_serde_json};
+use serde_json::{json, Value};
+
+///
+/// Parse `source` into a [`serde_json::Value`].
+///
+fn to_json(source: &str) -> Value {
+    to_serde_json(&SourceFile::dummy_file(source)).unwrap()
+}
+
+#[test]
+fn round_trip() {
+    let document = r#"
+        // A small config file.
+        {
+            name: 'avjason',
+            version: 1,
+            tags: ['json5', "parser",],
+            ratio: +.25,
+            offset: -0x10,
+            nested: { enabled: true, parent: null },
+        }
+    "#;
+
+    let value = to_json(document);
+    assert_eq!(
+        value,
+        json!({
+            "name": "avjason",
+            "version": 1,
+            "tags": ["json5", "parser"],
+            "ratio": 0.25,
+            "offset": -16,
+            "nested": { "enabled": true, "parent": null },
+        })
+    );
+
+    // Plain JSON out is valid JSON5 in.
+    assert_eq!(to_json(&value.to_string()), value);
+}
+
+#[test]
+fn numbers() {
+    assert!(to_json("42").is_u64());
+    assert!(to_json("-42").is_i64());
+    assert!(to_json("1e3").is_u64());
+    assert!(to_json("1.0").is_f64());
+    assert!(to_json("1e20").is_f64());
+    assert_eq!(to_json("-0x8000000000000000"), json!(i64::MIN));
+
+    let zero = to_json("-0");
+    assert!(zero.is_f64());
+    assert!(zero.as_f64().unwrap().is_sign_negative());
+    assert!(to_json("-0x0").as_f64().unwrap().is_sign_negative());
+    assert!(to_json("0").is_u64());
+}
+
+#[test]
+fn non_finite() {
+    assert_eq!(
+        to_json("[NaN, Infinity, -Infinity, 1]"),
+        json!([null, null, null, 1])
+    );
+}
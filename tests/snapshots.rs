@@ -0,0 +1,67 @@
+use avjason::{
+    common::SourceFile,
+    lex::{lex_all_recovering, tokens::Token, InputElement},
+};
+use insta::assert_debug_snapshot;
+
+///
+/// Lex every element of `source`, which must have no errors.
+///
+fn elements(source: &str) -> Vec<InputElement> {
+    let (elements, errors) = lex_all_recovering(&SourceFile::dummy_file(source));
+    assert_eq!(errors, []);
+    elements
+}
+
+///
+/// Lex every token of `source`, leaving out whitespace and comments,
+/// along with what each one decodes to.
+///
+fn tokens(source: &str) -> Vec<(Token, String)> {
+    let src = SourceFile::dummy_file(source);
+    let (elements, errors) = lex_all_recovering(&src);
+    assert_eq!(errors, []);
+    elements
+        .into_iter()
+        .filter_map(|element| match element {
+            InputElement::Token(token) => Some(token),
+            _ => None,
+        })
+        .map(|token| {
+            let decoded = match &token {
+                Token::Number(number) => format!("{:?}", number.as_f64(&src).unwrap()),
+                Token::String(string) => string.value(&src).unwrap().into_owned(),
+                Token::Identifier(ident) => ident.name(&src).into_owned(),
+                Token::Punctuator(_) => String::new(),
+            };
+            (token, decoded)
+        })
+        .collect()
+}
+
+#[test]
+fn numbers() {
+    assert_debug_snapshot!(tokens(
+        "0 -12 +3.5 .5 5. 1e10 2E-3 0x1F -0XaB Infinity -Infinity NaN"
+    ));
+}
+
+#[test]
+fn strings_with_escapes() {
+    assert_debug_snapshot!(tokens(
+        r#"'it\'s' "say \"hi\"" '\x41B\n\t\0' "line \
+continued" '\uD83D\uDE00 \u00e9'"#
+    ));
+}
+
+#[test]
+fn comments() {
+    assert_debug_snapshot!(elements(
+        "// to the end of the line\n/* over\n two lines */ /**/ 1 // last"
+    ));
+}
+
+#[test]
+fn identifiers() {
+    assert_debug_snapshot!(tokens(r"a $b _c d1 ef café \u0061b null true"));
+}
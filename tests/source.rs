@@ -0,0 +1,310 @@
+#[cfg(feature = "std")]
+use std::io::{self, Cursor, ErrorKind, Read};
+use std::str::FromStr;
+
+#[cfg(feature = "std")]
+use avjason::{
+    common::{ReaderStream, StreamError},
+    lex::LexError,
+};
+use avjason::{
+    common::{SourceFile, SourceStream, Span, Spanned},
+    lex::{
+        tokens::{CloseBrace, LIdentifier, Number, OpenBrace, Punct, Token},
+        InputElement, Lex,
+    },
+};
+
+#[test]
+fn leading_bom_is_skipped() {
+    let src = SourceFile::dummy_file("\u{FEFF}{}");
+    let mut input = src.iter();
+
+    let open = InputElement::lex(&mut input).unwrap();
+    assert!(matches!(
+        open,
+        InputElement::Token(Token::Punctuator(Punct::OpenBrace(_)))
+    ));
+    assert_eq!(open.span(), Span::new(1, 2));
+
+    let close = InputElement::lex(&mut input).unwrap();
+    assert!(matches!(
+        close,
+        InputElement::Token(Token::Punctuator(Punct::CloseBrace(_)))
+    ));
+    assert!(input.is_eof());
+}
+
+#[test]
+fn other_bom_is_whitespace() {
+    let src = SourceFile::dummy_file("{\u{FEFF}}");
+    let mut input = src.iter();
+    input.next();
+
+    let bom = InputElement::lex(&mut input).unwrap();
+    assert!(matches!(bom, InputElement::WhiteSpace(_)));
+}
+
+#[test]
+fn line_index_with_mixed_terminators() {
+    let src = SourceFile::dummy_file("a\nb\r\nc\rd\u{2028}e\u{2029}f\n");
+    let index = src.line_index();
+
+    assert_eq!(index.line_count(), 7);
+
+    let locations: Vec<_> = "abcdef"
+        .chars()
+        .map(|ch| index.location(src.text().chars().position(|c| c == ch).unwrap()))
+        .collect();
+    assert_eq!(locations, [(1, 1), (2, 1), (3, 1), (4, 1), (5, 1), (6, 1)]);
+
+    // Both halves of `\r\n` end line 2.
+    assert_eq!(index.location(3), (2, 2));
+    assert_eq!(index.location(4), (2, 3));
+
+    // The end of the file is on the final (empty) line.
+    assert_eq!(index.location(src.char_len()), (7, 1));
+}
+
+#[test]
+fn line_index_columns() {
+    let src = SourceFile::dummy_file("{\n  💩: 'é',\n}");
+    let index = src.line_index();
+
+    assert_eq!(index.location(0), (1, 1));
+    assert_eq!(index.location(4), (2, 3));
+    assert_eq!(index.location(7), (2, 6));
+    assert_eq!(index.location(12), (3, 1));
+}
+
+#[test]
+fn peek_ahead() {
+    let src = SourceFile::dummy_file("Infinity");
+    let mut input = src.iter();
+
+    assert_eq!(input.peek_n(0), input.peek());
+    assert_eq!(input.peek_n(1), input.peek2());
+    assert_eq!(input.peek_n(7), Some('y'));
+    assert_eq!(input.peek_n(8), None);
+
+    assert!(input.peek_str("Infinity"));
+    assert!(input.peek_str("Inf"));
+    assert!(input.peek_str(""));
+    assert!(!input.peek_str("Infinityy"));
+    assert!(!input.peek_str("NaN"));
+
+    input.next();
+    assert!(!input.peek_str("Infinity"));
+    assert!(input.peek_str("nfinity"));
+
+    let src = SourceFile::dummy_file("💩é");
+    assert!(src.iter().peek_str("💩é"));
+}
+
+#[test]
+fn checkpoint_and_restore() {
+    let src = SourceFile::dummy_file("[1, Infinityx]");
+    let mut input = src.iter();
+    input.nth(3);
+
+    let checkpoint = input.checkpoint();
+    assert!(Number::lex(&mut input).is_err());
+    input.nth(3);
+    assert_ne!(input.offset(), 4);
+
+    input.restore(checkpoint);
+    assert_eq!(input.offset(), 4);
+    assert_eq!(input.peek(), Some('I'));
+
+    let id = LIdentifier::lex(&mut input).unwrap();
+    assert_eq!(id.raw(&src), "Infinityx");
+}
+
+#[test]
+fn source_from_str() {
+    let src: SourceFile = "{}".parse().unwrap();
+    let mut input = src.iter();
+
+    assert!(matches!(
+        Token::lex(&mut input),
+        Ok(Token::Punctuator(Punct::OpenBrace(_)))
+    ));
+    let close = Token::lex(&mut input).unwrap();
+    assert!(matches!(close, Token::Punctuator(Punct::CloseBrace(_))));
+    assert_eq!(close.span(), Span::new(1, 2));
+
+    assert_eq!(SourceFile::from("[]").text(), "[]");
+    assert_eq!(SourceFile::from(String::from("[]")).text(), "[]");
+}
+
+#[test]
+fn stream_from_str() {
+    let mut stream = SourceStream::from_str("{}").unwrap();
+    assert!(stream.peek::<OpenBrace>());
+    assert_eq!(stream.lex::<OpenBrace>().unwrap().span(), Span::new(0, 1));
+    assert_eq!(stream.offset(), 1);
+
+    // A cursor from the stream does not move it.
+    let mut input = stream.iter();
+    assert!(Number::lex(&mut input).is_err());
+    assert!(!stream.peek::<OpenBrace>());
+
+    let close = stream.lex::<CloseBrace>().unwrap();
+    assert_eq!(close.span(), Span::new(1, 2));
+    assert_eq!(stream.file().slice(close.span()), "}");
+    assert!(stream.is_eof());
+
+    let mut stream = SourceStream::from("\u{FEFF}[");
+    assert!(matches!(
+        stream.lex::<Token>(),
+        Ok(Token::Punctuator(Punct::OpenBracket(_)))
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn source_from_reader() {
+    let bytes = "{ a: 'é', b: [1] }".as_bytes().to_vec();
+    let src = SourceFile::from_reader(Cursor::new(bytes)).unwrap();
+    let mut input = src.iter();
+
+    let mut tokens = vec![];
+    while !input.is_eof() {
+        match InputElement::lex(&mut input).unwrap() {
+            InputElement::Token(token) => tokens.push(token.raw(&src).to_string()),
+            _ => continue,
+        }
+    }
+
+    assert_eq!(
+        tokens,
+        ["{", "a", ":", "'é'", ",", "b", ":", "[", "1", "]", "}"]
+    );
+
+    let invalid = Cursor::new(vec![b'{', 0xFF, b'}']);
+    let error = SourceFile::from_reader(invalid).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+///
+/// Hands out its bytes one at a time, so chars are split between reads.
+///
+#[cfg(feature = "std")]
+struct Trickle<'a>(&'a [u8]);
+
+#[cfg(feature = "std")]
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some((first, rest)) = self.0.split_first() else {
+            return Ok(0);
+        };
+
+        buf[0] = *first;
+        self.0 = rest;
+        Ok(1)
+    }
+}
+
+///
+/// Every token lexed from `stream`, as written.
+///
+#[cfg(feature = "std")]
+fn stream_tokens(mut stream: ReaderStream<impl Read>) -> Vec<String> {
+    let mut tokens = vec![];
+    while !stream.is_eof().unwrap() {
+        if let InputElement::Token(token) = stream.lex().unwrap() {
+            tokens.push(stream.file().slice(token.span()).to_string());
+        }
+    }
+
+    tokens
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_from_reader() {
+    let text = "\u{FEFF}{ a: 'é', b: [1e3, Infinity], c: '\\u00e9' /* end */ }";
+    let expected = [
+        "{",
+        "a",
+        ":",
+        "'é'",
+        ",",
+        "b",
+        ":",
+        "[",
+        "1e3",
+        ",",
+        "Infinity",
+        "]",
+        ",",
+        "c",
+        ":",
+        "'\\u00e9'",
+        "}",
+    ];
+
+    let bytes = text.as_bytes().to_vec();
+    assert_eq!(
+        stream_tokens(ReaderStream::new(Cursor::new(bytes))),
+        expected
+    );
+    assert_eq!(
+        stream_tokens(ReaderStream::new(Trickle(text.as_bytes()))),
+        expected
+    );
+
+    // Spans are the same as if the whole text had been loaded at once.
+    let mut stream = ReaderStream::new(Trickle(text.as_bytes()));
+    stream.lex::<InputElement>().unwrap();
+    stream.lex::<InputElement>().unwrap();
+    let a = stream.lex::<LIdentifier>().unwrap();
+    assert_eq!(a.span(), Span::new(3, 4));
+    assert_eq!(stream.offset(), 4);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_long_tokens() {
+    // Each spans many blocks, and `-` needs a long look ahead to tell
+    // whether it is part of `-Infinity`.
+    let text = format!(
+        "['{}', /* {} */ -Infinity\\u0061, -Infinity, -Infinit]",
+        "a".repeat(1 << 20),
+        "b".repeat(1 << 20),
+    );
+    let src = SourceFile::dummy_file(text.as_str());
+    let expected: Vec<_> = src
+        .tokens()
+        .map(|token| src.slice(token.unwrap().span()).to_string())
+        .collect();
+
+    assert_eq!(
+        stream_tokens(ReaderStream::new(Cursor::new(text.into_bytes()))),
+        expected
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_errors() {
+    let mut stream = ReaderStream::new(Trickle(b"'unterminated"));
+    assert!(matches!(
+        stream.lex::<Token>(),
+        Err(StreamError::Lex(LexError::UnterminatedString { .. }))
+    ));
+
+    let mut stream = ReaderStream::new(Cursor::new(vec![b'[', 0xFF, b']']));
+    assert!(matches!(
+        stream.lex::<Token>(),
+        Err(StreamError::Io(err)) if err.kind() == ErrorKind::InvalidData
+    ));
+
+    // A char cut off by the end of the input.
+    let mut stream = ReaderStream::new(Cursor::new("'é'".as_bytes()[..2].to_vec()));
+    assert!(matches!(stream.lex::<Token>(), Err(StreamError::Io(_))));
+
+    let mut stream = ReaderStream::new(Cursor::new(""));
+    assert!(stream.is_eof().unwrap());
+    assert!(!stream.peek::<Token>().unwrap());
+}
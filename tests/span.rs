@@ -0,0 +1,170 @@
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    lex::{InputElement, Lex},
+};
+
+#[test]
+fn overlapping_spans() {
+    let a = Span::from(0..=4);
+    let b = Span::from(3..=7);
+
+    assert_eq!(a.union(&b), Span::from(0..=7));
+    assert_eq!(a.intersection(&b), Some(Span::from(3..=4)));
+    assert_eq!(b.intersection(&a), Some(Span::from(3..=4)));
+}
+
+#[test]
+fn adjacent_spans() {
+    let a = Span::from(0..=1);
+    let b = Span::from(2..=3);
+
+    assert_eq!(a.union(&b), Span::from(0..=3));
+    assert_eq!(a.intersection(&b), None);
+}
+
+#[test]
+fn disjoint_spans() {
+    let a = Span::from(0..=1);
+    let b = Span::from(5..=6);
+
+    assert_eq!(a.union(&b), Span::from(0..=6));
+    assert_eq!(b.union(&a), Span::from(0..=6));
+    assert_eq!(a.intersection(&b), None);
+}
+
+#[test]
+fn nested_spans() {
+    let outer = Span::from(0..=9);
+    let inner = Span::from(3..=4);
+
+    assert_eq!(outer.union(&inner), outer);
+    assert_eq!(outer.intersection(&inner), Some(inner));
+    assert_eq!(outer.intersection(&Span::empty(3)), None);
+}
+
+#[test]
+fn raw_text() {
+    let src = SourceFile::dummy_file("[\"💩\", é]");
+    let mut input = src.iter();
+    let tokens: Vec<_> =
+        std::iter::from_fn(|| (!input.is_eof()).then(|| InputElement::lex(&mut input).unwrap()))
+            .collect();
+
+    let raw: Vec<_> = tokens.iter().map(|token| token.raw(&src)).collect();
+    assert_eq!(raw, ["[", "\"💩\"", ",", " ", "é", "]"]);
+
+    assert_eq!(Span::empty(0).raw(&src), "");
+    assert_eq!(Span::empty(3).raw(&src), "");
+    assert_eq!(Span::from(2..=2).raw(&src), "💩");
+    assert_eq!(Span::new(2, 9).raw(&src), "💩\", é]");
+}
+
+#[test]
+fn byte_offsets() {
+    // 💩 is four bytes, é is two.
+    let src = SourceFile::dummy_file("a💩é{}");
+
+    let offsets: Vec<_> = (0..=6).map(|i| src.byte_offset(i)).collect();
+    assert_eq!(offsets, [0, 1, 5, 7, 8, 9, 9]);
+
+    assert_eq!(Span::new(1, 2).byte_range(&src), 1..5);
+    assert_eq!(Span::new(0, 3).byte_range(&src), 0..7);
+    assert_eq!(Span::new(2, 3).byte_range(&src), 5..7);
+    assert_eq!(Span::empty(2).byte_range(&src), 5..5);
+    assert_eq!(&src.text()[Span::new(1, 3).byte_range(&src)], "💩é");
+}
+
+#[test]
+fn byte_offsets_in_long_files() {
+    let text = "é💩a\n".repeat(100);
+    let src = SourceFile::dummy_file(text.as_str());
+    assert_eq!(src.char_len(), 400);
+
+    let expected: Vec<_> = text.char_indices().map(|(i, _)| i).collect();
+    let offsets: Vec<_> = (0..400).map(|i| src.byte_offset(i)).collect();
+    assert_eq!(offsets, expected);
+    assert_eq!(src.byte_offset(400), text.len());
+    assert_eq!(src.byte_offset(1000), text.len());
+
+    let mut input = src.iter();
+    input.nth(128);
+    assert_eq!(input.peek(), Some('💩'));
+    assert_eq!(Span::from(129..=130).raw(&src), "💩a");
+}
+
+#[test]
+fn merge_and_to() {
+    let adjacent = (Span::new(0, 2), Span::new(2, 4));
+    assert_eq!(adjacent.0.merge(adjacent.1), Span::new(0, 4));
+    assert_eq!(adjacent.1.merge(adjacent.0), Span::new(0, 4));
+
+    // The gap between them is covered.
+    let apart = (Span::new(0, 2), Span::new(5, 7));
+    assert_eq!(apart.0.merge(apart.1), Span::new(0, 7));
+    assert_eq!(apart.0.to(apart.1), Span::new(0, 7));
+    assert_eq!(apart.1.to(apart.0), Span::new(0, 7));
+
+    assert_eq!(Span::empty(3).merge(Span::empty(3)), Span::empty(3));
+    assert_eq!(Span::empty(3).to(Span::new(5, 6)), Span::new(3, 6));
+}
+
+#[test]
+fn contains_offsets() {
+    let span = Span::new(2, 5);
+
+    assert!(!span.contains(1));
+    assert!(span.contains(2));
+    assert!(span.contains(4));
+    assert!(!span.contains(5));
+
+    assert!(!Span::empty(2).contains(2));
+}
+
+#[test]
+fn overlapping() {
+    let span = Span::new(2, 5);
+
+    assert!(span.overlaps(&Span::new(4, 8)));
+    assert!(span.overlaps(&Span::new(0, 3)));
+    assert!(span.overlaps(&Span::new(3, 4)));
+    assert!(span.overlaps(&span));
+
+    // Touching at `start` or `end` is not overlapping.
+    assert!(!span.overlaps(&Span::new(5, 8)));
+    assert!(!span.overlaps(&Span::new(0, 2)));
+    assert!(!span.overlaps(&Span::empty(3)));
+}
+
+#[test]
+fn display_with_line_index() {
+    let src = SourceFile::dummy_file("{\n  key: 'value',\r\n  list: [\n    1,\n  ],\n}");
+    let index = src.line_index();
+    let display = |span: Span| span.display_with(index).to_string();
+
+    assert_eq!(display(Span::new(0, 1)), "1:1-1:2");
+    assert_eq!(display(Span::new(4, 7)), "2:3-2:6");
+    assert_eq!(display(Span::new(9, 16)), "2:8-2:15");
+
+    // `[` to `]`, across lines.
+    assert_eq!(display(Span::new(27, 39)), "3:9-5:4");
+    assert_eq!(display(Span::empty(0)), "1:1-1:1");
+}
+
+#[test]
+fn spanned_wrappers() {
+    let span = Span::new(2, 5);
+
+    assert_eq!(Box::new(span).span(), span);
+    let boxed: Box<dyn Spanned> = Box::new(span);
+    assert_eq!(boxed.span(), span);
+
+    assert_eq!(Some(span).span(), span);
+    assert_eq!(None::<Span>.span(), Span::empty(0));
+
+    let spans = vec![Span::new(4, 6), Span::new(1, 2), Span::new(8, 9)];
+    assert_eq!(spans.span(), Span::new(1, 9));
+    assert_eq!(Vec::<Span>::new().span(), Span::empty(0));
+    assert_eq!(spans[1..].span(), Span::new(1, 9));
+    assert_eq!([Span::new(3, 4)].span(), Span::new(3, 4));
+    assert_eq!(<[Span; 0]>::default().span(), Span::empty(0));
+}
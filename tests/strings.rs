@@ -0,0 +1,174 @@
+use std::borrow::Cow;
+
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    lex::{
+        strings::{LString, QuoteStyle},
+        Lex, LexError,
+    },
+};
+
+///
+/// Lex a string literal which should cover the whole of `source`,
+/// and decode its value.
+///
+fn value(source: &str) -> Result<String, LexError> {
+    let src = SourceFile::dummy_file(source);
+    let mut input = src.iter();
+    let string = LString::lex(&mut input)?;
+    assert!(input.is_eof(), "{source:?} was not fully lexed");
+    string.value(&src).map(Cow::into_owned)
+}
+
+#[test]
+fn plain_strings() {
+    assert_eq!(value(r#""""#).unwrap(), "");
+    assert_eq!(value(r#""hello""#).unwrap(), "hello");
+    assert_eq!(value(r"'💩 é'").unwrap(), "💩 é");
+}
+
+#[test]
+fn single_escapes() {
+    assert_eq!(value(r#""a\nb""#).unwrap(), "a\nb");
+    assert_eq!(value(r#"'\b\f\n\r\t\v'"#).unwrap(), "\u{8}\u{C}\n\r\t\u{B}");
+    assert_eq!(value(r#"'\'\"\\'"#).unwrap(), "'\"\\");
+    assert_eq!(value(r"'\0'").unwrap(), "\0");
+}
+
+#[test]
+fn non_escapes() {
+    assert_eq!(value(r"'\q\é'").unwrap(), "qé");
+}
+
+#[test]
+fn hex_escapes() {
+    assert_eq!(value(r"'\x41'").unwrap(), "A");
+    assert_eq!(value(r"'\xe9'").unwrap(), "é");
+}
+
+#[test]
+fn unicode_escapes() {
+    assert_eq!(value(r"'\u0041'").unwrap(), "A");
+    assert_eq!(value(r"'\u00e9\u00E9'").unwrap(), "éé");
+}
+
+#[test]
+fn surrogate_pairs() {
+    assert_eq!(value(r"'\uD83D\uDCA9'").unwrap(), "💩");
+    assert_eq!(value(r"'a\uD83D\uDCA9b'").unwrap(), "a💩b");
+
+    for lone in [r"'\uD83D'", r"'\uDCA9'", r"'\uD83Dx'", r"'\uD83D\u0041'"] {
+        let error = value(lone).unwrap_err();
+        assert!(error.message().contains("surrogate"), "{lone}: {error:?}");
+    }
+
+    // The error covers the escapes which could not be paired.
+    assert_eq!(value(r"'\uD83D'").unwrap_err().span(), Span::new(1, 7));
+    assert_eq!(value(r"'\uDCA9'").unwrap_err().span(), Span::new(1, 7));
+    assert_eq!(
+        value(r"'\uD83D\u0041'").unwrap_err().span(),
+        Span::new(1, 13)
+    );
+}
+
+#[test]
+fn invalid_escapes() {
+    for source in [r"'\1'", r"'\01'", r"'\x4'", r"'\u004'"] {
+        assert!(value(source).is_err(), "{source} should not lex");
+    }
+}
+
+#[test]
+fn line_continuations() {
+    assert_eq!(value("\"abc\\\ndef\"").unwrap(), "abcdef");
+    assert_eq!(value("'abc\\\r\ndef'").unwrap(), "abcdef");
+    assert_eq!(value("'abc\\\rdef'").unwrap(), "abcdef");
+    assert_eq!(value("'abc\\\u{2028}def'").unwrap(), "abcdef");
+}
+
+#[test]
+fn quote_styles() {
+    let lex = |source: &str| LString::lex(&mut SourceFile::dummy_file(source).iter());
+
+    assert_eq!(lex(r#""double""#).unwrap().quote(), QuoteStyle::Double);
+    assert_eq!(lex("'single'").unwrap().quote(), QuoteStyle::Single);
+
+    // The other quote is just part of the string.
+    assert_eq!(value(r#""it's""#).unwrap(), "it's");
+    assert_eq!(value(r#"'say "hi"'"#).unwrap(), "say \"hi\"");
+
+    assert!(lex(r#"'mismatched""#).is_err());
+    assert!(lex(r#""mismatched'"#).is_err());
+}
+
+#[test]
+fn unterminated_strings() {
+    let error = value(r#""abc"#).unwrap_err();
+    assert!(matches!(error, LexError::UnterminatedString { .. }));
+    assert_eq!(error.message(), "unterminated string literal");
+    assert_eq!(error.span(), Span::new(0, 4));
+
+    let error = value(r"'abc\").unwrap_err();
+    assert_eq!(error.span(), Span::new(0, 5));
+
+    for source in ["'abc\ndef'", "'abc\r\ndef'", "'abc\rdef'"] {
+        let error = value(source).unwrap_err();
+        assert_eq!(error.message(), "unterminated string literal");
+        assert_eq!(error.span(), Span::new(0, 4), "{source:?}");
+    }
+
+    assert_eq!(
+        value("'a\u{2028}b\u{2029}c'").unwrap(),
+        "a\u{2028}b\u{2029}c"
+    );
+}
+
+#[test]
+fn decoded_lengths() {
+    let src = SourceFile::dummy_file(
+        r"'ab\n\u0041\uD83D\uDCA9é\
+'",
+    );
+    let string = LString::lex(&mut src.iter()).unwrap();
+
+    assert_eq!(string.char_len(&src), Ok(6));
+    assert_eq!(string.byte_len(&src), Ok(10));
+    assert_eq!(string.value(&src).unwrap().len(), 10);
+}
+
+#[test]
+fn plain_values_are_borrowed() {
+    let lex = |source: &str| {
+        let src = SourceFile::dummy_file(source);
+        let string = LString::lex(&mut src.iter()).unwrap();
+        (src, string)
+    };
+
+    let (src, plain) = lex(r#""plain""#);
+    assert!(matches!(plain.value(&src), Ok(Cow::Borrowed("plain"))));
+
+    let (src, escaped) = lex(r#""a\nb""#);
+    assert!(matches!(escaped.value(&src), Ok(Cow::Owned(value)) if value == "a\nb"));
+
+    let (src, continued) = lex("'a\\\nb'");
+    assert!(matches!(continued.value(&src), Ok(Cow::Owned(value)) if value == "ab"));
+}
+
+#[test]
+fn control_characters() {
+    let error = value("'a\tb'").unwrap_err();
+    assert!(matches!(
+        error,
+        LexError::UnescapedControlChar { found: '\t', .. }
+    ));
+    assert_eq!(error.span(), Span::new(2, 3));
+    assert!(error.message().contains("U+0009"), "{error:?}");
+
+    assert!(value("'\u{0}'").is_err());
+    assert!(value("'\u{1F}'").is_err());
+
+    assert_eq!(value(r"'a\tb'").unwrap(), "a\tb");
+    assert_eq!(value(r"'\x01'").unwrap(), "\u{1}");
+    assert_eq!(value(r"'\u0001'").unwrap(), "\u{1}");
+    assert_eq!(value("'\u{7F}\u{80}'").unwrap(), "\u{7F}\u{80}");
+}
@@ -0,0 +1,159 @@
+use std::any::TypeId;
+
+use avjason::{
+    common::{SourceFile, Span, Spanned},
+    lex::{
+        tokens::{
+            CloseBracket, Colon, LIdentifier, LString, Minus, Number, OpenBrace, Punct, Token,
+        },
+        InputElement, Lex, LexError,
+    },
+    Token,
+};
+
+///
+/// Lex every token in `source`, which has no trivia.
+///
+fn tokens(source: &str) -> Vec<Token> {
+    let src = SourceFile::dummy_file(source);
+    let mut input = src.iter();
+    let mut tokens = vec![];
+    while !input.is_eof() {
+        tokens.push(Token::lex(&mut input).unwrap());
+    }
+
+    tokens
+}
+
+#[test]
+fn sign_and_dot_punctuators() {
+    let tokens = tokens("-+.");
+    assert!(matches!(
+        tokens.as_slice(),
+        [
+            Token::Punctuator(Punct::Minus(_)),
+            Token::Punctuator(Punct::Plus(_)),
+            Token::Punctuator(Punct::Dot(_)),
+        ]
+    ));
+
+    let spans: Vec<_> = tokens.iter().map(Spanned::span).collect();
+    assert_eq!(spans, [Span::new(0, 1), Span::new(1, 2), Span::new(2, 3)]);
+
+    let src = SourceFile::dummy_file("-");
+    assert!(matches!(Punct::lex(&mut src.iter()), Ok(Punct::Minus(_))));
+}
+
+#[test]
+fn token_peek() {
+    let peek = |source: &str| Token::peek(&SourceFile::dummy_file(source).iter());
+
+    for source in [
+        "1", "'a'", "\"a\"", "{", "]", "name", "-1", ".5", "Infinity",
+    ] {
+        assert!(peek(source), "{source:?} should start a token");
+    }
+
+    for source in [
+        " ",
+        "\t",
+        "\n",
+        "// comment",
+        "/* comment */",
+        "",
+        "#",
+        "\\",
+    ] {
+        assert!(!peek(source), "{source:?} should not start a token");
+    }
+}
+
+#[test]
+fn punct_peek() {
+    let peek = |source: &str| Punct::peek(&SourceFile::dummy_file(source).iter());
+
+    for source in ["{", "}", "[", "]", ":", ",", ".", "-", "+"] {
+        assert!(peek(source), "{source:?} should start a punctuator");
+    }
+
+    for source in ["a", "1", "'", "", " "] {
+        assert!(!peek(source), "{source:?} should not start a punctuator");
+    }
+
+    let src = SourceFile::dummy_file("a");
+    assert!(Punct::lex(&mut src.iter()).is_err());
+}
+
+#[test]
+fn punct_display() {
+    let source = "{}[]:,.-+";
+    let src = SourceFile::dummy_file(source);
+    let mut input = src.iter();
+
+    for expected in source.chars() {
+        let punct = Punct::lex(&mut input).unwrap();
+        assert_eq!(punct.as_char(), expected);
+        assert_eq!(punct.to_string(), expected.to_string());
+    }
+}
+
+#[test]
+fn token_macro() {
+    fn same<A: 'static, B: 'static>() -> bool {
+        TypeId::of::<A>() == TypeId::of::<B>()
+    }
+
+    assert!(same::<Token!['{'], OpenBrace>());
+    assert!(same::<Token![']'], CloseBracket>());
+    assert!(same::<Token![:], Colon>());
+    assert!(same::<Token![-], Minus>());
+    assert!(same::<Token![str], LString>());
+    assert!(same::<Token![num], Number>());
+    assert!(same::<Token![ident], LIdentifier>());
+    assert!(!same::<Token![str], Number>());
+
+    let src = SourceFile::dummy_file("'a'");
+    let string = <Token![str]>::lex(&mut src.iter()).unwrap();
+    assert_eq!(string.value(&src).unwrap(), "a");
+}
+
+#[test]
+fn tokens_iterator() {
+    let src = SourceFile::dummy_file("{\n  // comment\n  a: [1, 'two', null],\n}");
+
+    let raw: Vec<_> = src
+        .tokens()
+        .map(|token| token.unwrap().raw(&src).to_owned())
+        .collect();
+    assert_eq!(
+        raw,
+        ["{", "a", ":", "[", "1", ",", "'two'", ",", "null", "]", ",", "}"]
+    );
+
+    let elements: Vec<_> = src.tokens_with_trivia().map(Result::unwrap).collect();
+    assert_eq!(elements.len(), 21);
+    assert!(matches!(elements[2], InputElement::WhiteSpace(_)));
+    assert!(matches!(elements[3], InputElement::Comment(_)));
+    assert_eq!(
+        elements
+            .iter()
+            .filter(|element| !element.is_trivia())
+            .count(),
+        raw.len()
+    );
+}
+
+#[test]
+fn tokens_iterator_errors() {
+    let src = SourceFile::dummy_file("[1, 0123, 'open");
+    let tokens: Vec<_> = src.tokens().collect();
+
+    // Recovering from the octal literal skips the comma after it, too.
+    assert_eq!(tokens.len(), 5);
+    assert!(tokens[..3].iter().all(Result::is_ok));
+    assert!(matches!(tokens[3], Err(LexError::LegacyOctal { .. })));
+    assert!(matches!(
+        tokens[4],
+        Err(LexError::UnterminatedString { .. })
+    ));
+}
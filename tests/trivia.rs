@@ -0,0 +1,141 @@
+use avjason::{
+    common::{SourceFile, Spanned},
+    lex::{
+        tokens::{Punct, Token},
+        trivia::{CommentAttachment, TokenWithTrivia, WithTrivia},
+        InputElement,
+    },
+};
+
+const SOURCE: &str = "[\n    1, // one\n    // two\n    2\n]";
+
+fn lex(src: &SourceFile, attachment: CommentAttachment) -> Vec<TokenWithTrivia> {
+    WithTrivia::new(src.iter(), attachment)
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+fn text(src: &SourceFile, el: &impl Spanned) -> String {
+    el.raw(src).to_string()
+}
+
+fn comments(src: &SourceFile, trivia: &[InputElement]) -> Vec<String> {
+    trivia
+        .iter()
+        .filter(|el| matches!(el, InputElement::Comment(_)))
+        .map(|el| text(src, el))
+        .collect()
+}
+
+#[test]
+fn same_line_comment_is_trailing() {
+    let src = SourceFile::dummy_file(SOURCE);
+    let tokens = lex(&src, CommentAttachment::default());
+
+    let texts: Vec<_> = tokens.iter().map(|t| text(&src, t)).collect();
+    assert_eq!(texts, ["[", "1", ",", "2", "]"]);
+
+    let comma = &tokens[2];
+    assert_eq!(comments(&src, &comma.trailing), ["// one"]);
+    assert!(comments(&src, &comma.leading).is_empty());
+
+    let two = &tokens[3];
+    assert_eq!(comments(&src, &two.leading), ["// two"]);
+    assert!(comments(&src, &two.trailing).is_empty());
+}
+
+#[test]
+fn own_line_comments_without_same_line_trailing() {
+    let src = SourceFile::dummy_file(SOURCE);
+    let tokens = lex(
+        &src,
+        CommentAttachment {
+            same_line_trailing: false,
+            ..Default::default()
+        },
+    );
+
+    assert!(tokens[2].trailing.is_empty());
+    assert_eq!(comments(&src, &tokens[3].leading), ["// one", "// two"]);
+}
+
+#[test]
+fn trailing_only() {
+    let src = SourceFile::dummy_file(SOURCE);
+    let tokens = lex(
+        &src,
+        CommentAttachment {
+            leading: false,
+            trailing: true,
+            same_line_trailing: false,
+        },
+    );
+
+    assert_eq!(comments(&src, &tokens[2].trailing), ["// one", "// two"]);
+    assert!(tokens.iter().all(|t| t.leading.is_empty()));
+}
+
+#[test]
+fn no_trivia_is_lost() {
+    let src = SourceFile::dummy_file(SOURCE);
+    let tokens = lex(&src, CommentAttachment::default());
+
+    let rebuilt: String = tokens
+        .iter()
+        .flat_map(|t| {
+            t.leading
+                .iter()
+                .map(|el| text(&src, el))
+                .chain([text(&src, &t.token)])
+                .chain(t.trailing.iter().map(|el| text(&src, el)))
+        })
+        .collect();
+    assert_eq!(rebuilt, SOURCE);
+}
+
+#[test]
+fn comment_before_open_brace_is_leading() {
+    let src = SourceFile::dummy_file("/* config */\n{ a: 1 } // done");
+    let tokens = lex(&src, CommentAttachment::default());
+
+    let open = &tokens[0];
+    assert!(matches!(open.token, Token::Punctuator(Punct::OpenBrace(_))));
+    assert_eq!(comments(&src, &open.leading), ["/* config */"]);
+    assert!(open
+        .trailing
+        .iter()
+        .all(|el| !matches!(el, InputElement::Comment(_))));
+
+    let close = tokens.last().unwrap();
+    assert_eq!(comments(&src, &close.trailing), ["// done"]);
+}
+
+#[test]
+fn counts_line_breaks() {
+    for (source, expected) in [("a\nb\nc", 2), ("a\nb\r\nc\n", 3), ("a /* \n */ b", 0)] {
+        let src = SourceFile::dummy_file(source);
+        let mut tokens = WithTrivia::new(src.iter(), CommentAttachment::default());
+        assert_eq!(tokens.line_breaks(), 0);
+
+        for token in tokens.by_ref() {
+            token.unwrap();
+        }
+
+        assert_eq!(tokens.line_breaks(), expected, "{source:?}");
+    }
+}
+
+#[test]
+fn trivia_before_an_error_is_dropped() {
+    let src = SourceFile::dummy_file("1 // one\n'unterminated");
+    let mut tokens = WithTrivia::new(src.iter(), CommentAttachment::default());
+
+    // A clone carries on from the same place.
+    let rest: Vec<_> = tokens.clone().collect();
+    let one = tokens.next().unwrap().unwrap();
+    assert_eq!(text(&src, &one), "1");
+    assert!(one.trailing.is_empty());
+    assert!(tokens.next().unwrap().is_err());
+    assert!(tokens.next().is_none());
+    assert_eq!(rest.len(), 2);
+}
@@ -0,0 +1,116 @@
+use avjason::{common::SourceFile, parse::parse, value::Value};
+
+fn value(source: &str) -> Value {
+    parse(&SourceFile::dummy_file(source)).unwrap()
+}
+
+#[test]
+fn typed_array_extraction() {
+    let numbers = Value::Array(vec![
+        Value::Number(1.0),
+        Value::Number(2.0),
+        Value::Number(3.0),
+    ]);
+    assert_eq!(numbers.as_typed_array::<f64>(), Some(vec![1.0, 2.0, 3.0]));
+
+    let mixed = Value::Array(vec![Value::Number(1.0), Value::String("x".into())]);
+    assert_eq!(mixed.as_typed_array::<f64>(), None);
+    assert_eq!(mixed.as_typed_array::<String>(), None);
+
+    assert_eq!(Value::Null.as_typed_array::<f64>(), None);
+}
+
+#[test]
+fn accessors() {
+    let value = value("{ name: 'avjason', ratio: .5, debug: true, tags: ['a'], none: null }");
+
+    assert_eq!(value["name"].as_str(), Some("avjason"));
+    assert_eq!(value["ratio"].as_f64(), Some(0.5));
+    assert_eq!(value["debug"].as_bool(), Some(true));
+    assert_eq!(
+        value["tags"].as_array(),
+        Some(&vec![Value::String("a".into())])
+    );
+    assert_eq!(value["tags"][0].as_str(), Some("a"));
+    assert!(value["none"].is_null());
+    assert_eq!(value.as_object().map(|object| object.len()), Some(5));
+
+    // The wrong type.
+    assert_eq!(value["name"].as_f64(), None);
+    assert_eq!(value["ratio"].as_str(), None);
+    assert_eq!(value["tags"].as_object(), None);
+    assert_eq!(value.as_array(), None);
+    assert_eq!(value["tags"].get("a"), None);
+
+    // Missing keys and indices.
+    assert_eq!(value.get("missing"), None);
+    assert!(value["missing"]["deeper"].is_null());
+    assert!(value["tags"][1].is_null());
+    assert!(value[0].is_null());
+}
+
+#[test]
+fn ordered_keys() {
+    let value = value("{ zebra: 1, apple: 2, mango: 3, apple: 4 }");
+    let object = value.as_object().unwrap();
+
+    // A repeated key keeps its first position, with its last value.
+    let keys: Vec<_> = object.keys().map(String::as_str).collect();
+    assert_eq!(keys, ["zebra", "apple", "mango"]);
+    let values: Vec<_> = object.values().filter_map(Value::as_f64).collect();
+    assert_eq!(values, [1.0, 4.0, 3.0]);
+
+    // Order does not matter for equality.
+    assert_eq!(value, self::value("{ mango: 3, apple: 4, zebra: 1 }"));
+}
+
+#[test]
+fn pointers() {
+    let value = value(
+        "{
+            servers: [
+                { host: 'a', ports: [80, 443] },
+                { host: 'b', ports: [] },
+            ],
+            'a/b': { '~tilde': true, '': 'empty' },
+        }",
+    );
+
+    assert_eq!(value.pointer(""), Some(&value));
+    assert_eq!(
+        value.pointer("/servers/0/host").and_then(Value::as_str),
+        Some("a")
+    );
+    assert_eq!(
+        value.pointer("/servers/0/ports/1").and_then(Value::as_f64),
+        Some(443.0)
+    );
+    assert_eq!(
+        value.pointer("/servers/1/ports"),
+        Some(&Value::Array(vec![]))
+    );
+
+    // Escaped segments, and an empty key.
+    assert_eq!(
+        value.pointer("/a~1b/~0tilde").and_then(Value::as_bool),
+        Some(true)
+    );
+    assert_eq!(
+        value.pointer("/a~1b/").and_then(Value::as_str),
+        Some("empty")
+    );
+
+    // Missing paths.
+    for missing in [
+        "/missing",
+        "/servers/2",
+        "/servers/0/ports/1/deeper",
+        "/servers/01",
+        "/servers/-",
+        "/servers/+1",
+        "/a/b",
+        "servers",
+    ] {
+        assert_eq!(value.pointer(missing), None, "{missing:?}");
+    }
+}